@@ -0,0 +1,194 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{NaiveDateTime, NaiveTime};
+
+// 墙上时间 和 单调时间 相差超过这个值，就认为时钟跳变了（休眠、手动改时间）
+pub const CLOCK_JUMP_THRESHOLD: Duration = Duration::from_secs(2);
+
+// 剩余时间按秒显示时向上取整：还剩 4.3 秒显示 5，到了截止时间才显示 0
+pub fn display_seconds(remaining: Duration) -> u32 {
+    let millis = remaining.as_millis();
+    millis.div_ceil(1000) as u32
+}
+
+// 截止时间，例如 17:30 或 17:30:15；已经过了的话，就是明天的这个时间
+pub fn parse_deadline(text: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    let time = NaiveTime::parse_from_str(text, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()?;
+
+    let today = now.date().and_time(time);
+    if today > now {
+        Some(today)
+    } else {
+        Some(today + chrono::Duration::days(1))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClockJump {
+    // 墙上时间比单调时间走得多：系统休眠，或者时间被往后调
+    Forward(Duration),
+    // 时间被往前调
+    Backward(Duration),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tick {
+    pub remaining: Duration,
+    pub jump: Option<ClockJump>,
+}
+
+// 按截止时间计算剩余时间，而不是每次累加
+//
+// 单调时间 (Instant) 在系统休眠时不走，所以截止时间用墙上时间 (SystemTime)；
+// 同时记录单调时间，用来发现时钟跳变：
+// - 往后跳（休眠）：截止时间不变，休眠的时间也算在倒计时里；
+// - 往前跳（改时间）：截止时间跟着往前移，剩余时间不会变多。
+pub struct Countdown {
+    deadline: SystemTime,
+    last_wall: SystemTime,
+    last_mono: Instant,
+}
+
+impl Countdown {
+    pub fn new(remaining: Duration) -> Self {
+        Self::start_at(remaining, SystemTime::now(), Instant::now())
+    }
+
+    pub fn start_at(remaining: Duration, wall: SystemTime, mono: Instant) -> Self {
+        Self {
+            deadline: wall + remaining,
+            last_wall: wall,
+            last_mono: mono,
+        }
+    }
+
+    pub fn tick(&mut self) -> Tick {
+        self.tick_at(SystemTime::now(), Instant::now())
+    }
+
+    pub fn tick_at(&mut self, wall: SystemTime, mono: Instant) -> Tick {
+        let mono_elapsed = mono.saturating_duration_since(self.last_mono);
+
+        let jump = match wall.duration_since(self.last_wall) {
+            Ok(wall_elapsed) => wall_elapsed
+                .checked_sub(mono_elapsed)
+                .filter(|d| *d > CLOCK_JUMP_THRESHOLD)
+                .map(ClockJump::Forward),
+            // 墙上时间倒退了，小的倒退也要修正截止时间，只是不报告
+            Err(e) => {
+                let backward = e.duration() + mono_elapsed;
+                self.deadline -= backward;
+                Some(ClockJump::Backward(backward)).filter(|_| backward > CLOCK_JUMP_THRESHOLD)
+            }
+        };
+
+        self.last_wall = wall;
+        self.last_mono = mono;
+
+        Tick {
+            remaining: self.deadline.duration_since(wall).unwrap_or(Duration::ZERO),
+            jump,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(remaining: u64) -> (Countdown, SystemTime, Instant) {
+        let wall = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mono = Instant::now();
+        (
+            Countdown::start_at(Duration::from_secs(remaining), wall, mono),
+            wall,
+            mono,
+        )
+    }
+
+    #[test]
+    fn normal_ticks_count_down_without_jumps() {
+        let (mut countdown, wall, mono) = start(10);
+        let tick = countdown.tick_at(wall + Duration::from_secs(3), mono + Duration::from_secs(3));
+        assert_eq!(tick.remaining, Duration::from_secs(7));
+        assert_eq!(tick.jump, None);
+    }
+
+    #[test]
+    fn forward_jump_keeps_the_deadline() {
+        // 休眠 60 秒：墙上时间走了 61 秒，单调时间只走了 1 秒
+        let (mut countdown, wall, mono) = start(300);
+        let tick = countdown.tick_at(
+            wall + Duration::from_secs(61),
+            mono + Duration::from_secs(1),
+        );
+        assert_eq!(tick.jump, Some(ClockJump::Forward(Duration::from_secs(60))));
+        assert_eq!(tick.remaining, Duration::from_secs(239));
+    }
+
+    #[test]
+    fn backward_jump_moves_the_deadline() {
+        // 时间被往前调了 30 秒，同时真的过了 1 秒：剩余时间只少 1 秒，不会多出 30 秒
+        let (mut countdown, wall, mono) = start(300);
+        let tick = countdown.tick_at(
+            wall - Duration::from_secs(30),
+            mono + Duration::from_secs(1),
+        );
+        assert_eq!(
+            tick.jump,
+            Some(ClockJump::Backward(Duration::from_secs(31)))
+        );
+        assert_eq!(tick.remaining, Duration::from_secs(299));
+    }
+
+    #[test]
+    fn small_drift_is_not_reported() {
+        let (mut countdown, wall, mono) = start(300);
+        let tick = countdown.tick_at(
+            wall + Duration::from_millis(2500),
+            mono + Duration::from_millis(1000),
+        );
+        assert_eq!(tick.jump, None);
+        let tick = countdown.tick_at(
+            wall + Duration::from_millis(2000),
+            mono + Duration::from_millis(1500),
+        );
+        assert_eq!(tick.jump, None);
+    }
+
+    #[test]
+    fn deadline_expires_at_zero() {
+        let (mut countdown, wall, mono) = start(5);
+        let tick = countdown.tick_at(
+            wall + Duration::from_millis(4300),
+            mono + Duration::from_millis(4300),
+        );
+        assert_eq!(display_seconds(tick.remaining), 1);
+
+        let tick = countdown.tick_at(wall + Duration::from_secs(5), mono + Duration::from_secs(5));
+        assert_eq!(tick.remaining, Duration::ZERO);
+        assert_eq!(display_seconds(tick.remaining), 0);
+
+        // 过了截止时间也不会变成负数
+        let tick = countdown.tick_at(wall + Duration::from_secs(9), mono + Duration::from_secs(9));
+        assert_eq!(tick.remaining, Duration::ZERO);
+    }
+
+    #[test]
+    fn deadline_in_the_past_is_tomorrow() {
+        let now = chrono::NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_opt(18, 0, 0)
+            .unwrap();
+        let deadline = parse_deadline("17:30", now).unwrap();
+        assert_eq!(deadline - now, chrono::Duration::minutes(23 * 60 + 30));
+        assert_eq!(
+            parse_deadline("18:00:30", now).unwrap() - now,
+            chrono::Duration::seconds(30)
+        );
+        assert_eq!(parse_deadline("7pm", now), None);
+    }
+}
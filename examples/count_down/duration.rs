@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+
+use crate::clock::parse_deadline;
+
+// 分、秒、截止时间输入框的解析；不依赖 fltk，可以单独测试
+
+// 输入框允许的最大时长 99:59
+pub const MAX_MINUTES: u32 = 99;
+pub const MAX_COUNTDOWN: u32 = MAX_MINUTES * 60 + 59;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DurationError {
+    InvalidMinutes,
+    InvalidSeconds,
+    Zero,
+    TooLong,
+    InvalidDeadline,
+}
+
+// 输入框里的一个数字：不能为空，不能是负数
+pub fn parse_field(text: &str) -> Option<u32> {
+    text.trim().parse::<u32>().ok()
+}
+
+// 分、秒 -> 总秒数；秒数超过 59 的会进位，例如 0:90 -> 1:30
+pub fn parse_duration(minutes: &str, seconds: &str) -> Result<u32, DurationError> {
+    let minutes = parse_field(minutes).ok_or(DurationError::InvalidMinutes)?;
+    let seconds = parse_field(seconds).ok_or(DurationError::InvalidSeconds)?;
+
+    let countdown = minutes
+        .checked_mul(60)
+        .and_then(|m| m.checked_add(seconds))
+        .ok_or(DurationError::TooLong)?;
+
+    if countdown == 0 {
+        return Err(DurationError::Zero);
+    }
+    if countdown > MAX_COUNTDOWN {
+        return Err(DurationError::TooLong);
+    }
+
+    Ok(countdown)
+}
+
+// 截止时间模式：填了截止时间，就倒计时到那个时间，忽略分、秒
+pub fn parse_countdown(
+    minutes: &str,
+    seconds: &str,
+    deadline: &str,
+    now: NaiveDateTime,
+) -> Result<Duration, DurationError> {
+    if deadline.trim().is_empty() {
+        return parse_duration(minutes, seconds)
+            .map(|countdown| Duration::from_secs(countdown as u64));
+    }
+
+    parse_deadline(deadline, now)
+        .and_then(|deadline| (deadline - now).to_std().ok())
+        .ok_or(DurationError::InvalidDeadline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn noon() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        assert_eq!(parse_duration("", "30"), Err(DurationError::InvalidMinutes));
+        assert_eq!(
+            parse_duration("1", "  "),
+            Err(DurationError::InvalidSeconds)
+        );
+    }
+
+    #[test]
+    fn non_digits_are_rejected() {
+        assert_eq!(
+            parse_duration("1a", "0"),
+            Err(DurationError::InvalidMinutes)
+        );
+        assert_eq!(
+            parse_duration("-1", "0"),
+            Err(DurationError::InvalidMinutes)
+        );
+        assert_eq!(
+            parse_duration("1", "3.5"),
+            Err(DurationError::InvalidSeconds)
+        );
+    }
+
+    #[test]
+    fn seconds_carry_into_minutes() {
+        assert_eq!(parse_duration("0", "90"), Ok(90));
+        assert_eq!(parse_duration(" 2 ", "05"), Ok(125));
+    }
+
+    #[test]
+    fn out_of_range_seconds_are_too_long() {
+        assert_eq!(parse_duration("99", "59"), Ok(MAX_COUNTDOWN));
+        assert_eq!(parse_duration("99", "60"), Err(DurationError::TooLong));
+        assert_eq!(parse_duration("0", "6000"), Err(DurationError::TooLong));
+    }
+
+    #[test]
+    fn zero_duration_is_rejected() {
+        assert_eq!(parse_duration("0", "0"), Err(DurationError::Zero));
+        assert_eq!(parse_duration("00", "000"), Err(DurationError::Zero));
+    }
+
+    #[test]
+    fn overflow_is_too_long_not_a_panic() {
+        let max = u32::MAX.to_string();
+        assert_eq!(parse_duration(&max, "0"), Err(DurationError::TooLong));
+        assert_eq!(parse_duration("0", &max), Err(DurationError::TooLong));
+        assert_eq!(
+            parse_duration("99999999999", "0"),
+            Err(DurationError::InvalidMinutes)
+        );
+    }
+
+    #[test]
+    fn deadline_overrides_minutes_and_seconds() {
+        assert_eq!(
+            parse_countdown("x", "y", "12:30", noon()),
+            Ok(Duration::from_secs(30 * 60))
+        );
+        // 已经过了的时间算明天
+        assert_eq!(
+            parse_countdown("1", "0", "11:00", noon()),
+            Ok(Duration::from_secs(23 * 3600))
+        );
+        assert_eq!(
+            parse_countdown("1", "0", "25:00", noon()),
+            Err(DurationError::InvalidDeadline)
+        );
+        assert_eq!(
+            parse_countdown("1", "0", " ", noon()),
+            Ok(Duration::from_secs(60))
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use std::fmt;
+use std::fs;
+
+// 键盘、鼠标滚轮 -> 计时器动作 的映射；不依赖 fltk，方便单独测试
+//
+// 配置文件每行一个绑定：`按键 = 动作 [参数]`，# 开头是注释
+//   Ctrl+Shift+Up = seconds +5
+//   0             = set_minutes 10
+//   LeftWheelUp   = minutes -1
+
+pub const KEYMAP_FILE_PATH: &str = "./assets/keymap.conf";
+
+// 默认的绑定就是仓库里的配置文件，只有一份，不会两边改得不一样
+pub const DEFAULT_KEYMAP: &str = include_str!("../../assets/keymap.conf");
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TimerAction {
+    AddMinutes(i32),
+    AddSeconds(i32),
+    SetMinutes(u32),
+    StartPause,
+    ToggleMiniMode,
+    ToggleNotify,
+    ShowHelp,
+}
+
+impl TimerAction {
+    // 调整时长的动作，只有在没开始计时时才生效
+    pub fn is_adjustment(&self) -> bool {
+        matches!(
+            self,
+            TimerAction::AddMinutes(_) | TimerAction::AddSeconds(_) | TimerAction::SetMinutes(_)
+        )
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split_whitespace();
+        let name = parts.next().ok_or("missing action")?;
+        let arg = parts.next();
+
+        if let Some(extra) = parts.next() {
+            return Err(format!("unexpected `{}`", extra));
+        }
+
+        let number = |arg: Option<&str>| -> Result<i32, String> {
+            let arg = arg.ok_or(format!("`{}` needs a number", name))?;
+            arg.trim_start_matches('+')
+                .parse::<i32>()
+                .map_err(|_| format!("`{}` is not a number", arg))
+        };
+
+        let action = match name {
+            "minutes" => TimerAction::AddMinutes(number(arg)?),
+            "seconds" => TimerAction::AddSeconds(number(arg)?),
+            "set_minutes" => {
+                let minutes = number(arg)?;
+                if minutes < 0 {
+                    return Err(format!("`{}` can not be negative", minutes));
+                }
+                TimerAction::SetMinutes(minutes as u32)
+            }
+            "start" | "mini" | "notify" | "help" if arg.is_some() => {
+                return Err(format!("`{}` takes no argument", name))
+            }
+            "start" => TimerAction::StartPause,
+            "mini" => TimerAction::ToggleMiniMode,
+            "notify" => TimerAction::ToggleNotify,
+            "help" => TimerAction::ShowHelp,
+            _ => return Err(format!("unknown action `{}`", name)),
+        };
+
+        Ok(action)
+    }
+}
+
+impl fmt::Display for TimerAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerAction::AddMinutes(n) => write!(f, "{:+} min", n),
+            TimerAction::AddSeconds(n) => write!(f, "{:+} sec", n),
+            TimerAction::SetMinutes(n) => write!(f, "set {} min", n),
+            TimerAction::StartPause => write!(f, "start / pause"),
+            TimerAction::ToggleMiniMode => write!(f, "mini mode"),
+            TimerAction::ToggleNotify => write!(f, "desktop notification on / off"),
+            TimerAction::ShowHelp => write!(f, "show this help"),
+        }
+    }
+}
+
+// 滚轮在窗口的左半边 or 右半边
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WheelSide {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trigger {
+    // name 是规范化的名字：单个字符小写，其它按键首字母大写，例如 "m"、"Up"、"Enter"
+    Key {
+        name: String,
+        ctrl: bool,
+        shift: bool,
+    },
+    Wheel {
+        side: WheelSide,
+        up: bool,
+    },
+}
+
+pub const KEY_NAMES: [&str; 9] = [
+    "Up", "Down", "Left", "Right", "Enter", "Space", "Tab", "Home", "End",
+];
+
+impl Trigger {
+    fn parse(text: &str) -> Result<Self, String> {
+        let wheel = match text {
+            "LeftWheelUp" => Some((WheelSide::Left, true)),
+            "LeftWheelDown" => Some((WheelSide::Left, false)),
+            "RightWheelUp" => Some((WheelSide::Right, true)),
+            "RightWheelDown" => Some((WheelSide::Right, false)),
+            _ => None,
+        };
+        if let Some((side, up)) = wheel {
+            return Ok(Trigger::Wheel { side, up });
+        }
+
+        let mut ctrl = false;
+        let mut shift = false;
+        let mut parts: Vec<&str> = text.split('+').map(|p| p.trim()).collect();
+        let key = parts.pop().unwrap_or_default();
+
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" => ctrl = true,
+                "shift" => shift = true,
+                _ => return Err(format!("unknown modifier `{}`", modifier)),
+            }
+        }
+
+        let name = if key.chars().count() == 1 {
+            key.to_lowercase()
+        } else if let Some(name) = KEY_NAMES.iter().find(|n| n.eq_ignore_ascii_case(key)) {
+            name.to_string()
+        } else {
+            return Err(format!("unknown key `{}`", key));
+        };
+
+        Ok(Trigger::Key { name, ctrl, shift })
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Trigger::Key { name, ctrl, shift } => {
+                if *ctrl {
+                    write!(f, "Ctrl+")?;
+                }
+                if *shift {
+                    write!(f, "Shift+")?;
+                }
+                write!(f, "{}", name)
+            }
+            Trigger::Wheel { side, up } => write!(
+                f,
+                "Wheel {} ({})",
+                if *up { "up" } else { "down" },
+                if *side == WheelSide::Left {
+                    "left half"
+                } else {
+                    "right half"
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub trigger: Trigger,
+    pub action: TimerAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::parse(DEFAULT_KEYMAP).expect("default keymap is valid")
+    }
+}
+
+impl Keymap {
+    // 出错时报告行号；同一个按键写了两次，后面的覆盖前面的
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut bindings: Vec<Binding> = vec![];
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (trigger, action) = line
+                .split_once('=')
+                .ok_or(format!("line {}: expected `key = action`", index + 1))?;
+
+            let trigger =
+                Trigger::parse(trigger.trim()).map_err(|e| format!("line {}: {}", index + 1, e))?;
+            let action = TimerAction::parse(action.trim())
+                .map_err(|e| format!("line {}: {}", index + 1, e))?;
+
+            bindings.retain(|b| b.trigger != trigger);
+            bindings.push(Binding { trigger, action });
+        }
+
+        Ok(Keymap { bindings })
+    }
+
+    // 没有配置文件就用默认的；配置文件有错，提示后也用默认的
+    pub fn load(path: &str) -> Self {
+        match fs::read_to_string(path) {
+            Ok(text) => Keymap::parse(&text).unwrap_or_else(|e| {
+                eprintln!("{}: {}, using the default keymap", path, e);
+                Keymap::default()
+            }),
+            Err(_) => Keymap::default(),
+        }
+    }
+
+    pub fn key_action(&self, name: &str, ctrl: bool, shift: bool) -> Option<TimerAction> {
+        self.bindings.iter().find_map(|b| match &b.trigger {
+            Trigger::Key {
+                name: n,
+                ctrl: c,
+                shift: s,
+            } if n == name && *c == ctrl && *s == shift => Some(b.action),
+            _ => None,
+        })
+    }
+
+    pub fn wheel_action(&self, side: WheelSide, up: bool) -> Option<TimerAction> {
+        let trigger = Trigger::Wheel { side, up };
+        self.bindings
+            .iter()
+            .find(|b| b.trigger == trigger)
+            .map(|b| b.action)
+    }
+
+    // 帮助窗口里显示的文本，一行一个绑定
+    pub fn help_text(&self) -> String {
+        self.bindings
+            .iter()
+            .map(|b| format!("{:<22}{}", b.trigger.to_string(), b.action))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keymap_parses() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.key_action("up", false, false),
+            None,
+            "key names are normalized, \"up\" is not \"Up\""
+        );
+        assert_eq!(
+            keymap.key_action("Up", false, false),
+            Some(TimerAction::AddMinutes(1))
+        );
+        assert_eq!(
+            keymap.key_action("Up", true, true),
+            Some(TimerAction::AddSeconds(5))
+        );
+        assert_eq!(
+            keymap.wheel_action(WheelSide::Right, false),
+            Some(TimerAction::AddSeconds(1))
+        );
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_skipped() {
+        let keymap = Keymap::parse("# comment\n\n   # indented comment\nm = mini\n").unwrap();
+        assert_eq!(keymap.bindings.len(), 1);
+        assert_eq!(
+            keymap.key_action("m", false, false),
+            Some(TimerAction::ToggleMiniMode)
+        );
+    }
+
+    #[test]
+    fn key_names_are_normalized() {
+        let keymap = Keymap::parse("ctrl+shift+ENTER = start\nM = help").unwrap();
+        assert_eq!(
+            keymap.key_action("Enter", true, true),
+            Some(TimerAction::StartPause)
+        );
+        assert_eq!(
+            keymap.key_action("m", false, false),
+            Some(TimerAction::ShowHelp)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_and_actions_report_the_line() {
+        assert_eq!(
+            Keymap::parse("m = mini\nPageUp = start"),
+            Err("line 2: unknown key `PageUp`".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("Alt+m = mini"),
+            Err("line 1: unknown modifier `Alt`".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("m = fly"),
+            Err("line 1: unknown action `fly`".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("m mini"),
+            Err("line 1: expected `key = action`".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("m = minutes x"),
+            Err("line 1: `x` is not a number".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("m = start now"),
+            Err("line 1: `start` takes no argument".to_string())
+        );
+        assert_eq!(
+            Keymap::parse("m = set_minutes -1"),
+            Err("line 1: `-1` can not be negative".to_string())
+        );
+    }
+
+    #[test]
+    fn duplicate_bindings_keep_the_last_one() {
+        let keymap = Keymap::parse("m = mini\nn = notify\nM = help").unwrap();
+        assert_eq!(keymap.bindings.len(), 2);
+        assert_eq!(
+            keymap.key_action("m", false, false),
+            Some(TimerAction::ShowHelp)
+        );
+        // 被覆盖的绑定挪到最后，帮助里按文件顺序显示
+        assert_eq!(
+            keymap.bindings[1].trigger,
+            Trigger::Key {
+                name: "m".to_string(),
+                ctrl: false,
+                shift: false
+            }
+        );
+    }
+
+    #[test]
+    fn missing_or_broken_file_falls_back_to_the_defaults() {
+        assert_eq!(
+            Keymap::load("./does/not/exist/keymap.conf"),
+            Keymap::default()
+        );
+
+        let path = std::env::temp_dir().join(format!("keymap-test-{}.conf", std::process::id()));
+        fs::write(&path, "m = mini\nbroken line\n").unwrap();
+        let keymap = Keymap::load(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(keymap, Keymap::default());
+    }
+}
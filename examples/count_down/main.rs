@@ -0,0 +1,1033 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use fltk::prelude::{InputExt, WidgetBase, WidgetExt};
+use fltk::{
+    app,
+    app::MouseWheel,
+    button::Button,
+    enums,
+    enums::{Color, Event, Key},
+    frame::Frame,
+    group,
+    group::Flex,
+    input,
+    input::{Input, IntInput},
+    prelude::*,
+    window::Window,
+};
+
+use chrono::Local;
+use rodio::{source::Source, Decoder, OutputStream};
+
+use hello_fltk::widgets::StyledButton;
+
+mod clock;
+mod duration;
+mod keymap;
+mod notify;
+mod urgency;
+
+use clock::{display_seconds, ClockJump, Countdown};
+use duration::{parse_countdown, parse_field, DurationError, MAX_COUNTDOWN, MAX_MINUTES};
+use keymap::{Keymap, TimerAction, WheelSide, KEYMAP_FILE_PATH};
+
+const WINDOW_WIDTH: i32 = 300;
+// 高度里包括数字下面 16 像素的提示行
+const WINDOW_HEIGHT: i32 = 156;
+pub const EXPANDED_WINDOW_HEIGHT: i32 = 206;
+
+// mini 模式下只显示数字
+const MINI_WINDOW_WIDTH: i32 = 190;
+const MINI_WINDOW_HEIGHT: i32 = 70;
+
+#[derive(Debug, Copy, Clone)]
+pub enum ChannelMessage {
+    StartCounter(Duration),
+
+    UpdateCountdown(u32, bool),
+
+    PauseCountdown,
+    StopCountdown,
+
+    StartClicked,
+
+    ToggleMiniMode,
+    ToggleHelp,
+    ToggleNotify,
+
+    // 倒计时正常结束（不是 STOP）
+    Expired,
+
+    // 计时线程发现系统时钟跳变（休眠、改时间）
+    ClockJumped(ClockJump),
+
+    // 分、秒输入框有变化，需要重新校验
+    InputChanged,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum State {
+    Start,
+    Pause,
+    Resume(u64),
+}
+
+// 校验输入框，不合法的标红；返回是否可以开始计时
+fn validate_inputs(
+    input_minutes: &mut IntInput,
+    input_seconds: &mut IntInput,
+    input_deadline: &mut Input,
+) -> bool {
+    let result = parse_countdown(
+        &input_minutes.value(),
+        &input_seconds.value(),
+        &input_deadline.value(),
+        Local::now().naive_local(),
+    );
+
+    let (minutes_ok, seconds_ok, deadline_ok) = match result {
+        Ok(_) => (true, true, true),
+        Err(DurationError::InvalidMinutes) => {
+            (false, parse_field(&input_seconds.value()).is_some(), true)
+        }
+        Err(DurationError::InvalidSeconds) => (true, false, true),
+        Err(DurationError::Zero) | Err(DurationError::TooLong) => (false, false, true),
+        Err(DurationError::InvalidDeadline) => (true, true, false),
+    };
+
+    for (input, ok) in [(input_minutes, minutes_ok), (input_seconds, seconds_ok)] {
+        input.set_color(if ok { Color::DarkMagenta } else { Color::Red });
+        input.redraw();
+    }
+    input_deadline.set_color(if deadline_ok {
+        Color::DarkMagenta
+    } else {
+        Color::Red
+    });
+    input_deadline.redraw();
+
+    result.is_ok()
+}
+
+fn to_minutes_seconds(countdown: u32) -> (String, String) {
+    let mut seconds = countdown;
+    let mut minutes = 0;
+
+    if seconds >= 60 {
+        minutes = seconds / 60;
+    }
+
+    seconds = seconds - (minutes * 60);
+
+    let mut minutes_string = minutes.to_string();
+    if minutes < 10 {
+        minutes_string = "0".to_string() + &minutes.to_string();
+    }
+
+    let mut seconds_string = seconds.to_string();
+    if seconds < 10 {
+        seconds_string = "0".to_string() + &seconds.to_string();
+    }
+
+    (minutes_string, seconds_string)
+}
+
+pub fn play_sound() {
+    std::thread::spawn(|| {
+        let alarm_file_path = "./assets/default_alarm.wav".to_owned();
+        let (_stream, stream_handle) = OutputStream::try_default().unwrap();
+
+        // done: BufReader 和 File 都没有 clone
+        for _ in 1..=10 {
+            let file = BufReader::new(File::open(alarm_file_path.clone()).unwrap());
+
+            let source = Decoder::new(file).unwrap();
+            stream_handle
+                .play_raw(source.convert_samples())
+                .expect("Failed to play audio");
+
+            std::thread::sleep(std::time::Duration::from_millis(167));
+        }
+    });
+}
+
+fn update_countdown(frame: &mut Frame, countdown: u32, update_background: bool) {
+    // 截止时间模式可能超过 1 小时，显示成 h:mm:ss
+    if countdown >= 3600 {
+        let (minutes, seconds) = to_minutes_seconds(countdown % 3600);
+        frame.set_label(&format!("{}:{}:{}", countdown / 3600, minutes, seconds));
+    } else {
+        let (minutes, seconds) = to_minutes_seconds(countdown);
+        frame.set_label(&format!("{}:{}", minutes, seconds));
+    }
+
+    if update_background == false {
+        return;
+    }
+
+    if countdown > 20 {
+        frame.set_label_color(Color::White);
+    } else if countdown >= 10 {
+        frame.set_label_color(Color::Blue);
+    } else if countdown > 0 {
+        frame.set_label_color(Color::Red);
+    } else {
+        play_sound();
+    }
+}
+
+// 窗口背景 红、黑 交替闪烁，最后恢复成黑色
+fn flash_window(window: &Window, times: u32) {
+    let mut window = window.clone();
+    let mut count = 0;
+
+    app::add_timeout3(0.0, move |handle| {
+        window.set_color(if count % 2 == 0 {
+            Color::Red
+        } else {
+            Color::Black
+        });
+        window.redraw();
+
+        count += 1;
+        if count < times * 2 {
+            app::repeat_timeout3(0.3, handle);
+        }
+    });
+}
+
+fn style_input_fields<I: InputExt>(input: &mut I) {
+    input.set_color(Color::DarkMagenta);
+    input.set_text_color(Color::White);
+    input.set_selection_color(Color::Blue);
+    input.set_text_size(22);
+}
+
+// DONE: 状态改变
+// 1. start, set;
+// 2. start -> pause -> resume; start + set -> stop;
+// 3. stop/set -> disable + 显示 input；
+
+// todo: 如果当 button 用，而不是先取得 member
+struct StartButton {
+    button: StyledButton,
+}
+
+impl StartButton {
+    fn new(
+        input_minutes: &IntInput,
+        input_seconds: &IntInput,
+        input_deadline: &Input,
+        window: &Window,
+        flex: &Flex,
+        thread_tx: mpsc::Sender<ChannelMessage>,
+        tx: app::Sender<ChannelMessage>,
+        remain_millis: Arc<Mutex<u64>>,
+    ) -> Self {
+        let mut button = StyledButton::pill(0, 0, "Start");
+        let mut state = State::Pause;
+
+        button.set_color(Color::Blue);
+        // 键盘交给 keymap，例如回车是 start；按钮拿着焦点的话回车会按下它自己
+        button.visible_focus(false);
+
+        button.set_label_color(Color::Black);
+        button.set_label_font(enums::Font::HelveticaBold);
+        button.set_label_size(18);
+
+        // 供 callback 使用；如果 new 的参数不是 ref，那就需要在调用 new 的地方 clone，这里就不需要再 clone；
+        let mut input_mm = input_minutes.clone();
+        let mut input_ss = input_seconds.clone();
+        let input_end = input_deadline.clone();
+        let mut window_clone = window.clone();
+        let mut flex_clone = flex.clone();
+
+        button.set_callback(move |_button| {
+            // 回车键会直接 do_callback，不管按钮是否 disable；时长不合法就不开始
+            let countdown = parse_countdown(
+                &input_mm.value(),
+                &input_ss.value(),
+                &input_end.value(),
+                Local::now().naive_local(),
+            );
+            if _button.label() == "Start" && countdown.is_err() {
+                return;
+            }
+
+            _button.set_color(Color::Blue);
+
+            if _button.label() == "Start" {
+                state = State::Start;
+            }
+
+            // 只要点击了，就窗口变小，隐藏输入框
+            flex_clone.hide();
+            window_clone.set_size(WINDOW_WIDTH, WINDOW_HEIGHT);
+            window_clone.set_color(Color::Black);
+
+            tx.send(ChannelMessage::StartClicked);
+
+            // 1. start -> (pause -> resume)，2. set 按钮，才会重置成 start
+            match state {
+                State::Start => {
+                    _button.set_label("Pause");
+                    state = State::Pause;
+
+                    _button.set_color(Color::Green);
+
+                    let duration = countdown.unwrap();
+
+                    // 秒数进位后，写回输入框，例如 90 秒 -> 1 分 30 秒
+                    if input_end.value().trim().is_empty() {
+                        let countdown = duration.as_secs();
+                        input_mm.set_value(&(countdown / 60).to_string());
+                        input_ss.set_value(&(countdown % 60).to_string());
+                    }
+
+                    // 通知线程 计时开始
+                    thread_tx
+                        .send(ChannelMessage::StartCounter(duration))
+                        .expect("Failed to start the counter.");
+                }
+
+                State::Pause => {
+                    // 状态可附加数据
+                    state = State::Resume(*(remain_millis.lock().unwrap()));
+
+                    _button.set_label("Resume");
+
+                    // 发送消息
+                    thread_tx
+                        .send(ChannelMessage::PauseCountdown)
+                        .expect("Failed to get stop countdown message");
+
+                    return;
+                }
+
+                State::Resume(mills) => {
+                    state = State::Pause;
+
+                    println!("resume: the counter left: {}", mills);
+
+                    _button.set_label("Pause");
+                    _button.set_color(Color::Green);
+
+                    // 发送消息, resume 和 start 完全一样，因为 剩余时间是全局变量
+                    thread_tx
+                        .send(ChannelMessage::StartCounter(Duration::from_millis(mills)))
+                        .expect("Failed to get stop countdown message");
+                }
+            }
+        });
+
+        Self { button }
+    }
+}
+
+// todo: 带有状态 button
+struct ResetButton {
+    input_show: bool,
+    button: StyledButton,
+}
+
+// todo: 参数 &impl WindowExt 则不行；
+impl ResetButton {
+    fn new(
+        start_button: &StyledButton,
+        window: &Window,
+        flex: &Flex,
+        thread_tx: mpsc::Sender<ChannelMessage>,
+    ) -> Self {
+        let mut button = StyledButton::pill(0, 0, "Set");
+
+        button.set_color(Color::Red);
+        button.visible_focus(false);
+
+        button.set_label_color(Color::Black);
+        button.set_label_font(enums::Font::HelveticaBold);
+        button.set_label_size(18);
+
+        let mut start_button = start_button.clone();
+        let mut local_window = window.clone();
+        let mut flex = flex.clone();
+
+        button.set_callback(move |_button| {
+            // 只把 按钮文本 改了，并没有 改变按钮的 状态；向 channel 发送消息改变；
+            start_button.set_label("Start");
+            start_button.set_color(Color::Blue);
+
+            // 把自己 disable 掉
+            _button.deactivate();
+            _button.set_color(Color::Gray0);
+
+            // 如果 hide 再 show，布局会乱掉
+            // _button.hide();
+
+            // 显示下方的 输入框
+            flex.show();
+            local_window.set_size(WINDOW_WIDTH, EXPANDED_WINDOW_HEIGHT);
+
+            thread_tx
+                .send(ChannelMessage::StopCountdown)
+                .expect("Failed to get stop countdown message");
+        });
+
+        Self {
+            input_show: false,
+            button,
+        }
+    }
+}
+
+// mini 模式：无边框、置顶的小窗口，只显示数字；拖动数字可以移动窗口
+// 用一个独立的窗口，因为 set_override 只能在 show 之前设置，而且无法取消
+struct MiniWindow {
+    window: Window,
+    label: Frame,
+}
+
+impl MiniWindow {
+    fn new(x: i32, y: i32, font: &str, keymap: Keymap, tx: app::Sender<ChannelMessage>) -> Self {
+        let mut window = Window::default()
+            .with_size(MINI_WINDOW_WIDTH, MINI_WINDOW_HEIGHT)
+            .with_pos(x, y);
+        window.set_color(Color::Black);
+
+        let mut label = Frame::default().size_of_parent();
+        label.set_label_size(48);
+        label.set_label_color(Color::White);
+        label.set_label_font(enums::Font::by_name(font));
+
+        window.end();
+
+        // 无边框，并且在所有窗口之上
+        window.set_override();
+
+        // 按下时，鼠标相对窗口的位置；拖动时，窗口跟着鼠标走
+        let mut offset_x = 0;
+        let mut offset_y = 0;
+        let mut window_clone = window.clone();
+
+        label.handle(move |_, ev| match ev {
+            Event::Push => {
+                // 双击回到正常模式
+                if app::event_clicks() {
+                    tx.send(ChannelMessage::ToggleMiniMode);
+                }
+
+                offset_x = app::event_x();
+                offset_y = app::event_y();
+                true
+            }
+            Event::Drag => {
+                // 窗口隐藏后再显示，还在原来的位置，所以位置不用另外保存
+                window_clone.set_pos(
+                    app::event_x_root() - offset_x,
+                    app::event_y_root() - offset_y,
+                );
+                true
+            }
+            Event::Released => true,
+            _ => false,
+        });
+
+        window.handle(move |_, ev| match ev {
+            Event::KeyUp => {
+                let action = key_name(app::event_key()).and_then(|name| {
+                    keymap.key_action(&name, app::is_event_ctrl(), app::is_event_shift())
+                });
+
+                if action == Some(TimerAction::ToggleMiniMode) {
+                    tx.send(ChannelMessage::ToggleMiniMode);
+                    return true;
+                }
+                false
+            }
+            _ => false,
+        });
+
+        Self { window, label }
+    }
+
+    // 和大窗口显示同样的数字、颜色
+    fn mirror(&mut self, frame: &Frame) {
+        self.label.set_label(&frame.label());
+        self.label.set_label_color(frame.label_color());
+        self.window.redraw();
+    }
+}
+
+// fltk 的按键 -> keymap 里的名字
+fn key_name(key: Key) -> Option<String> {
+    let named_keys = [
+        (Key::Up, "Up"),
+        (Key::Down, "Down"),
+        (Key::Left, "Left"),
+        (Key::Right, "Right"),
+        (Key::Enter, "Enter"),
+        (Key::KPEnter, "Enter"),
+        (Key::Tab, "Tab"),
+        (Key::Home, "Home"),
+        (Key::End, "End"),
+        (Key::from_char(' '), "Space"),
+    ];
+
+    if let Some((_, name)) = named_keys.iter().find(|(k, _)| *k == key) {
+        return Some(name.to_string());
+    }
+
+    key.to_char().map(|ch| ch.to_lowercase().to_string())
+}
+
+struct InputDeviceEvent {}
+
+impl InputDeviceEvent {
+    pub fn new(
+        window: &mut Window,
+        start_button: &StyledButton,
+        input_minutes: &IntInput,
+        input_seconds: &IntInput,
+        tx: app::Sender<ChannelMessage>,
+        keymap: Keymap,
+    ) {
+        let mut mm_input = input_minutes.clone();
+        let mut ss_input = input_seconds.clone();
+
+        // 这是一个内部函数，closure
+        let mut change_countdown = move |inc_mm: i32, inc_ss: i32| {
+            // 输入框可能是空的，或者不是数字，当作 0 处理
+            let mm = parse_field(&mm_input.value()).unwrap_or(0).min(MAX_MINUTES) as i32;
+            let ss = parse_field(&ss_input.value())
+                .unwrap_or(0)
+                .min(MAX_COUNTDOWN) as i32;
+
+            // 分钟单独变化，秒数不变
+            let new_mm = (mm + inc_mm).clamp(0, MAX_MINUTES as i32);
+
+            // 秒数变化时可以进位、借位，例如 0:59 -> 1:00
+            let countdown = (new_mm * 60 + ss + inc_ss).clamp(0, MAX_COUNTDOWN as i32);
+
+            mm_input.set_value(&(countdown / 60).to_string());
+            ss_input.set_value(&(countdown % 60).to_string());
+
+            tx.send(ChannelMessage::InputChanged);
+        };
+
+        const MIDDLE_OF_WINDOW: i32 = WINDOW_WIDTH / 2;
+
+        // 滚轮太快时，丢掉间隔太短的事件；不能 sleep，否则 ui 会卡住
+        const SCROLL_REST_TIME: Duration = Duration::from_millis(30);
+        let mut last_scroll: Option<Instant> = None;
+
+        let mut input_minutes_clone = input_minutes.clone();
+        let mut start_button = start_button.clone();
+
+        // 执行 keymap 里的动作
+        let mut run_action = move |action: TimerAction, window_height: i32| {
+            // 计时中，不能调整时长
+            if action.is_adjustment() && start_button.label() != "Start" {
+                return false;
+            }
+
+            match action {
+                TimerAction::AddMinutes(minutes) => change_countdown(minutes, 0),
+                TimerAction::AddSeconds(seconds) => change_countdown(0, seconds),
+                TimerAction::SetMinutes(minutes) => {
+                    // 输入框显示时，数字键是输入框的，不能抢
+                    if window_height >= EXPANDED_WINDOW_HEIGHT - 1 {
+                        return false;
+                    }
+
+                    input_minutes_clone.set_value(&minutes.min(MAX_MINUTES).to_string());
+                    tx.send(ChannelMessage::InputChanged);
+                }
+                TimerAction::StartPause => start_button.do_callback(),
+                TimerAction::ToggleMiniMode => tx.send(ChannelMessage::ToggleMiniMode),
+                TimerAction::ToggleNotify => tx.send(ChannelMessage::ToggleNotify),
+                TimerAction::ShowHelp => tx.send(ChannelMessage::ToggleHelp),
+            }
+            true
+        };
+
+        window.handle(move |local_window, ev| match ev {
+            Event::MouseWheel => {
+                if last_scroll.is_some_and(|t| t.elapsed() < SCROLL_REST_TIME) {
+                    return true;
+                }
+
+                // 鼠标的滚动，往上 or 往下；鼠标当前位置在窗口的左半边 or 右半边
+                let side = if app::event_x() < MIDDLE_OF_WINDOW {
+                    WheelSide::Left
+                } else {
+                    WheelSide::Right
+                };
+                let action = match app::event_dy() {
+                    MouseWheel::Up => keymap.wheel_action(side, true),
+                    MouseWheel::Down => keymap.wheel_action(side, false),
+                    _ => None,
+                };
+
+                match action {
+                    Some(action) if run_action(action, local_window.pixel_h()) => {
+                        last_scroll = Some(Instant::now());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+
+            Event::KeyUp => {
+                let Some(name) = key_name(app::event_key()) else {
+                    return false;
+                };
+
+                match keymap.key_action(&name, app::is_event_ctrl(), app::is_event_shift()) {
+                    Some(action) => run_action(action, local_window.pixel_h()),
+                    None => false,
+                }
+            }
+            _ => false,
+        });
+    }
+}
+
+// 帮助窗口，列出当前生效的快捷键
+struct HelpWindow {
+    window: Window,
+}
+
+impl HelpWindow {
+    fn new(keymap: &Keymap) -> Self {
+        let text = keymap.help_text();
+        let lines = text.lines().count() as i32;
+
+        let mut window = Window::default()
+            .with_size(320, lines * 16 + 20)
+            .with_label("Shortcuts");
+        window.set_color(Color::Black);
+
+        let mut label = Frame::default().size_of_parent();
+        label.set_label(&text);
+        label.set_label_font(enums::Font::Courier);
+        label.set_label_size(13);
+        label.set_label_color(Color::White);
+        label.set_align(enums::Align::Inside | enums::Align::Left | enums::Align::Top);
+
+        window.end();
+
+        // 任意键 or 点击，关闭
+        window.handle(|w, ev| match ev {
+            Event::KeyUp | Event::Push => {
+                w.hide();
+                true
+            }
+            _ => false,
+        });
+
+        Self { window }
+    }
+}
+
+fn main() {
+    let app = app::App::default();
+
+    let font = app.load_font("./assets/FiraCode-Regular.ttf").unwrap();
+
+    // 快捷键配置，没有配置文件就用默认的
+    let keymap = Keymap::load(KEYMAP_FILE_PATH);
+
+    // rx 用以更新 ui
+    let (tx, rx) = app::channel::<ChannelMessage>();
+
+    // 两个按钮之间的消息传递，channel 不需要 mutex，只需要传递 clone
+    let (thread_tx, thread_rx) = mpsc::channel::<ChannelMessage>();
+
+    // 全局就此一份，在多个线程中共享的（ui + spawn的多个）
+    // 类似 指针 + mutex（lock）
+    let remain_millis = Arc::new(Mutex::new(0 as u64));
+
+    let mut main_wnd = Window::default()
+        .with_size(WINDOW_WIDTH, WINDOW_HEIGHT)
+        .with_label("Timer")
+        .with_pos(50, 160);
+    main_wnd.set_color(Color::Black);
+
+    // 整个窗口的 布局，列布局，3 行
+    let mut layout = group::Flex::default().column().size_of_parent();
+    layout.set_margins(0, 20, 0, 20);
+    layout.end();
+
+    // 1. frame 只是显示数字
+    // let mut frame = Frame::default().with_pos(0, -23).size_of(&main_wnd);
+    let mut title_label = Frame::default();
+
+    title_label.set_label_size(70);
+    title_label.set_label_color(Color::White);
+    title_label.set_label_font(enums::Font::by_name(&font));
+
+    update_countdown(&mut title_label, 5, false);
+
+    // 数字下面一行小字：时钟跳变之类的提示；窗口标题留给通知开关
+    let mut status_frame = Frame::default();
+    status_frame.set_label_size(12);
+    status_frame.set_label_color(Color::Light1);
+
+    // 双击数字，切换到 mini 模式
+    title_label.handle(move |_, ev| match ev {
+        Event::Push => {
+            if app::event_clicks() {
+                tx.send(ChannelMessage::ToggleMiniMode);
+            }
+            true
+        }
+        _ => false,
+    });
+
+    // 2. 最底部 flex 包含两个数字输入框；创建时，y 的位置在窗口的下侧，所以看不见；
+    // DONE: 因为都是 flex 布局，所以不用设定大小 和 位置
+    let mut input_group = group::Flex::default().row();
+    // .with_pos(0, WINDOW_HEIGHT);
+    input_group.set_pad(12);
+    input_group.set_margins(20, 0, 20, 0);
+
+    let mut input_minutes = input::IntInput::default();
+    let mut input_seconds = input::IntInput::default();
+    input_minutes.set_value(&"0".to_owned());
+    input_seconds.set_value(&"5".to_owned());
+
+    // 截止时间，例如 17:30；空着就按 分、秒 倒计时
+    let mut input_deadline = input::Input::default();
+    input_deadline.set_tooltip("End time, e.g. 17:30. Leave empty to count down minutes/seconds.");
+
+    style_input_fields(&mut input_minutes);
+    style_input_fields(&mut input_seconds);
+    style_input_fields(&mut input_deadline);
+    input_group.fixed(&input_deadline, 100);
+
+    // 每次输入都校验一次
+    input_minutes.set_trigger(enums::CallbackTrigger::Changed);
+    input_minutes.emit(tx, ChannelMessage::InputChanged);
+    input_seconds.set_trigger(enums::CallbackTrigger::Changed);
+    input_seconds.emit(tx, ChannelMessage::InputChanged);
+    input_deadline.set_trigger(enums::CallbackTrigger::Changed);
+    input_deadline.emit(tx, ChannelMessage::InputChanged);
+
+    input_group.end();
+    input_group.hide();
+
+    // 3. 中间，两个按钮
+    let mut button_group = group::Flex::default().row();
+    button_group.set_pad(12);
+    button_group.set_margins(20, 0, 20, 0);
+
+    let start_button = StartButton::new(
+        &input_minutes,
+        &input_seconds,
+        &input_deadline,
+        &main_wnd,
+        &input_group,
+        thread_tx.clone(),
+        tx.clone(),
+        remain_millis.clone(),
+    );
+
+    let reset_button = ResetButton::new(&start_button.button, &main_wnd, &input_group, thread_tx);
+
+    button_group.end();
+    button_group.show();
+
+    // row 布局
+    layout.add(&title_label);
+    layout.add(&status_frame);
+    layout.add(&button_group);
+    layout.add(&input_group);
+    // 固定 2/3 高度，变动时，只变动 1 的高度
+    layout.fixed(&status_frame, 16);
+    layout.fixed(&button_group, 30);
+    layout.fixed(&input_group, 30);
+
+    layout.show();
+
+    // 没有这个，则显示不了 input；layout 是 main_wnd 的最外层
+    main_wnd.resizable(&layout);
+
+    InputDeviceEvent::new(
+        &mut main_wnd,
+        &start_button.button,
+        &input_minutes,
+        &input_seconds,
+        tx.clone(),
+        keymap.clone(),
+    );
+
+    main_wnd.end();
+    main_wnd.show();
+
+    let mut mini_wnd = MiniWindow::new(
+        main_wnd.x(),
+        main_wnd.y(),
+        &font,
+        keymap.clone(),
+        tx.clone(),
+    );
+    mini_wnd.mirror(&title_label);
+
+    let mut help_wnd = HelpWindow::new(&keymap);
+
+    // 第二个窗口
+    make_another_window();
+
+    // 启动一个后台线程，负责计时任务
+    std::thread::spawn(move || {
+        while let Ok(msg) = thread_rx.recv() {
+            match msg {
+                ChannelMessage::StartCounter(remains) => {
+                    println!("MSG: StartCounter.");
+
+                    // 按截止时间计时，休眠后醒来也是准的
+                    let mut countdown = Countdown::new(remains);
+                    loop {
+                        let tick = countdown.tick();
+
+                        if let Some(jump) = tick.jump {
+                            tx.send(ChannelMessage::ClockJumped(jump));
+                        }
+
+                        // 改变全局变量，resume 时的剩余时间
+                        {
+                            *(remain_millis.lock().unwrap()) = tick.remaining.as_millis() as u64;
+                        }
+
+                        // 向 ui 线程发送消息，附带参数；
+                        let seconds = display_seconds(tick.remaining);
+                        tx.send(ChannelMessage::UpdateCountdown(seconds, true));
+
+                        // todo: 读写的互斥量：暂停 就拿走，这里判断是否有，没有就等待，而不是退出循环；
+                        // todo: 外层循环 + revc ，里面又是 循环 + recv，对吗？
+                        // 退出内部循环，但是仍在 大的消息处理循环中
+                        if let Ok(ChannelMessage::PauseCountdown) = thread_rx.try_recv() {
+                            println!("in start loop: Got message to pause countdown");
+
+                            break;
+                        }
+
+                        // 先发送消息，再退出计时，保证 最终计时显示是 0
+                        if seconds == 0 {
+                            tx.send(ChannelMessage::Expired);
+                            break;
+                        }
+
+                        // todo: rx.recv 处理中，能否 sleep
+                        thread::sleep(Duration::from_millis(100));
+                    }
+                }
+
+                ChannelMessage::StopCountdown => {
+                    println!("MSG: StopCountdown.");
+                    println!("in msg loop: Got message to stop countdown");
+
+                    // 向 ui 线程发送消息，附带参数；
+                    tx.send(ChannelMessage::UpdateCountdown(0, true));
+                    // break;
+                }
+
+                _ => {
+                    println!("in msg loop: unknown msg");
+                }
+            }
+        }
+
+        println!("thread exit.");
+    });
+
+    let mut reset_button_clone = reset_button.button.clone();
+
+    // 倒计时结束时，是否发桌面通知；快捷键切换
+    let mut notify_enabled = true;
+    let mut start_button_clone = start_button.button.clone();
+
+    while app.wait() {
+        if let Some(msg) = rx.recv() {
+            match msg {
+                ChannelMessage::UpdateCountdown(countdown, update_background) => {
+                    update_countdown(&mut title_label, countdown, update_background);
+                    mini_wnd.mirror(&title_label);
+                }
+
+                ChannelMessage::InputChanged => {
+                    // 计时中，输入框是隐藏的，不用管
+                    if start_button_clone.label() != "Start" {
+                        continue;
+                    }
+
+                    if validate_inputs(&mut input_minutes, &mut input_seconds, &mut input_deadline)
+                    {
+                        start_button_clone.activate();
+                    } else {
+                        start_button_clone.deactivate();
+                    }
+                }
+
+                ChannelMessage::ClockJumped(jump) => {
+                    let label = match jump {
+                        ClockJump::Forward(d) => format!("clock jumped +{}s", d.as_secs()),
+                        ClockJump::Backward(d) => format!("clock jumped -{}s", d.as_secs()),
+                    };
+                    status_frame.set_label(&label);
+                }
+
+                ChannelMessage::Expired => {
+                    // 静音时只有声音没用：置顶、闪烁窗口，再发一个桌面通知
+                    let mut window = if mini_wnd.window.shown() {
+                        mini_wnd.window.clone()
+                    } else {
+                        main_wnd.clone()
+                    };
+                    window.show();
+                    flash_window(&window, 5);
+                    urgency::set_urgent(&window, true);
+
+                    if notify_enabled {
+                        notify::notify_expired(
+                            notify::SessionBus,
+                            format!("Countdown finished at {}", Local::now().format("%H:%M:%S")),
+                        );
+                    }
+                }
+
+                ChannelMessage::ToggleNotify => {
+                    notify_enabled = !notify_enabled;
+
+                    let label = if notify_enabled {
+                        "Timer"
+                    } else {
+                        "Timer (notification off)"
+                    };
+                    main_wnd.set_label(label);
+                }
+
+                ChannelMessage::ToggleHelp => {
+                    if help_wnd.window.shown() {
+                        help_wnd.window.hide();
+                    } else {
+                        // 显示在计时窗口的下方
+                        help_wnd
+                            .window
+                            .set_pos(main_wnd.x(), main_wnd.y() + main_wnd.h() + 30);
+                        help_wnd.window.show();
+                    }
+                }
+
+                ChannelMessage::ToggleMiniMode => {
+                    // 先 show 再 hide，保证始终有窗口显示，app.wait() 不会退出
+                    if mini_wnd.window.shown() {
+                        main_wnd.show();
+                        mini_wnd.window.hide();
+                    } else {
+                        mini_wnd.window.show();
+                        main_wnd.hide();
+                    }
+                }
+
+                ChannelMessage::StartClicked => {
+                    // 上一次计时的提示不再相关
+                    status_frame.set_label("");
+                    urgency::set_urgent(&main_wnd, false);
+                    urgency::set_urgent(&mini_wnd.window, false);
+                    reset_button_clone.set_label("STOP");
+                    reset_button_clone.set_color(Color::Red);
+                    reset_button_clone.activate();
+                }
+
+                _ => {
+                    println!("got something new.");
+                }
+            }
+        }
+    }
+
+    // app.run().unwrap();
+}
+
+fn make_another_window() {
+    let mut another_wnd = Window::default()
+        .with_size(300, 500)
+        .with_label("Another")
+        .with_pos(300, 400);
+
+    another_wnd.set_color(Color::Blue);
+
+    let mut label = Frame::default().with_label("Hello FLTK").with_pos(100, 50);
+    label.set_label_color(Color::Green);
+    label.set_label_size(40);
+
+    let mut row = group::Flex::default()
+        .column()
+        .with_pos(10, 100)
+        .with_size(100, 250);
+    row.set_margin(5);
+    row.set_pad(5);
+
+    // todo: 不起作用
+    row.set_color(Color::Yellow);
+
+    let mut b1 = Button::default().with_label("first @->");
+    let b2 = Button::default().with_label("second");
+    let mut b3 = Button::default().with_label("@<- third");
+
+    b1.set_color(Color::Red);
+    b1.set_label_color(Color::White);
+    b1.set_label_size(28);
+
+    b1.set_callback({
+        let mut b2_clone = b2.clone();
+        let row_clone = row.clone();
+        let mut wnd = another_wnd.clone();
+        let mut label1 = label.clone();
+        
+        move |_btn| {
+            b2_clone.hide();
+            row_clone.recalc();
+
+            wnd.set_color(Color::DarkMagenta);
+
+            label1.set_label_color(Color::White);
+            label1.set_color(Color::White);
+            label1.set_damage(true);
+        }
+    });
+
+    b3.set_callback({
+        let mut b2_another_clone = b2.clone();
+        let row_clone = row.clone();
+        let mut wnd = another_wnd.clone();
+        let mut label1 = label.clone();
+
+        move |_btn| {
+            b2_another_clone.show();
+            row_clone.recalc();
+
+            wnd.set_color(Color::DarkCyan);
+
+            label1.set_label_color(Color::Black);
+            label1.set_color(Color::White);
+            label1.set_damage(true);
+
+            // 需要调用上层的 redraw
+            // row_clone.redraw();
+            wnd.redraw();
+        }
+    });
+
+    row.fixed(&b3, 25);
+
+    row.end();
+
+    another_wnd.end();
+    another_wnd.show();
+}
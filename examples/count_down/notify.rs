@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::thread::{self, JoinHandle};
+
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+// freedesktop 桌面通知：org.freedesktop.Notifications.Notify
+// SessionBus 连 DBUS_SESSION_BUS_ADDRESS 指的 session bus；send_notification 只要一个连接，
+// 测试里用点对点连接接到一个假的通知服务上
+const NOTIFICATIONS_DESTINATION: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+
+const APP_NAME: &str = "Timer";
+const ICON_NAME: &str = "alarm-symbolic";
+
+// 紧急程度：0 low, 1 normal, 2 critical；critical 的通知不会自动消失
+const URGENCY_CRITICAL: u8 = 2;
+
+const EXPIRED_SUMMARY: &str = "Time is up";
+
+// 发通知的一方；正常是 session bus，测试里换成记录调用的替身
+pub trait Notifier {
+    // 返回通知的 id
+    fn notify(&self, summary: &str, body: &str) -> zbus::Result<u32>;
+}
+
+pub struct SessionBus;
+
+impl Notifier for SessionBus {
+    fn notify(&self, summary: &str, body: &str) -> zbus::Result<u32> {
+        let connection = Connection::session()?;
+        send_notification(&connection, summary, body)
+    }
+}
+
+fn notification_hints() -> HashMap<&'static str, Value<'static>> {
+    let mut hints = HashMap::new();
+    hints.insert("urgency", Value::U8(URGENCY_CRITICAL));
+    hints
+}
+
+// 返回通知的 id
+pub fn send_notification(connection: &Connection, summary: &str, body: &str) -> zbus::Result<u32> {
+    let hints = notification_hints();
+
+    let actions: Vec<&str> = vec![];
+    let replaces_id = 0u32;
+    let expire_timeout = -1i32;
+
+    let reply = connection.call_method(
+        Some(NOTIFICATIONS_DESTINATION),
+        NOTIFICATIONS_PATH,
+        Some(NOTIFICATIONS_DESTINATION),
+        "Notify",
+        &(
+            APP_NAME,
+            replaces_id,
+            ICON_NAME,
+            summary,
+            body,
+            actions,
+            hints,
+            expire_timeout,
+        ),
+    )?;
+
+    reply.body().deserialize::<u32>()
+}
+
+// 发送失败（例如 Windows 上没有 session bus）只打印错误，返回是否发出去了
+pub fn send_expired(notifier: &impl Notifier, body: &str) -> bool {
+    match notifier.notify(EXPIRED_SUMMARY, body) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("desktop notification failed: {}", e);
+            false
+        }
+    }
+}
+
+// 后台线程发送，D-Bus 调用会阻塞
+pub fn notify_expired<N>(notifier: N, body: String) -> JoinHandle<bool>
+where
+    N: Notifier + Send + 'static,
+{
+    thread::spawn(move || send_expired(&notifier, &body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    // 记下每次的 summary 和 body；fail 为 true 时像没有 session bus 一样报错
+    #[derive(Clone, Default)]
+    struct FakeNotifier {
+        sent: Arc<Mutex<Vec<(String, String)>>>,
+        fail: bool,
+    }
+
+    impl Notifier for FakeNotifier {
+        fn notify(&self, summary: &str, body: &str) -> zbus::Result<u32> {
+            if self.fail {
+                return Err(zbus::Error::Failure("no session bus".to_string()));
+            }
+            let mut sent = self.sent.lock().unwrap();
+            sent.push((summary.to_string(), body.to_string()));
+            Ok(sent.len() as u32)
+        }
+    }
+
+    #[test]
+    fn expired_notification_has_summary_and_body() {
+        let notifier = FakeNotifier::default();
+        assert!(send_expired(&notifier, "Countdown finished at 12:00:00"));
+        assert_eq!(
+            *notifier.sent.lock().unwrap(),
+            vec![(
+                "Time is up".to_string(),
+                "Countdown finished at 12:00:00".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn failure_is_reported_not_a_panic() {
+        let notifier = FakeNotifier {
+            fail: true,
+            ..FakeNotifier::default()
+        };
+        assert!(!send_expired(&notifier, "body"));
+        assert!(notifier.sent.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sent_from_a_background_thread() {
+        let notifier = FakeNotifier::default();
+        let handle = notify_expired(notifier.clone(), "body".to_string());
+        assert!(handle.join().unwrap());
+        assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+    }
+
+    // 假的 org.freedesktop.Notifications，记下收到的参数
+    #[cfg(unix)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct NotifyCall {
+        app_name: String,
+        replaces_id: u32,
+        app_icon: String,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+        hints: HashMap<String, zbus::zvariant::OwnedValue>,
+        expire_timeout: i32,
+    }
+
+    #[cfg(unix)]
+    struct FakeServer {
+        calls: Arc<Mutex<Vec<NotifyCall>>>,
+    }
+
+    #[cfg(unix)]
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl FakeServer {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            app_name: String,
+            replaces_id: u32,
+            app_icon: String,
+            summary: String,
+            body: String,
+            actions: Vec<String>,
+            hints: HashMap<String, zbus::zvariant::OwnedValue>,
+            expire_timeout: i32,
+        ) -> u32 {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(NotifyCall {
+                app_name,
+                replaces_id,
+                app_icon,
+                summary,
+                body,
+                actions,
+                hints,
+                expire_timeout,
+            });
+            calls.len() as u32 + 40
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn notify_call_on_the_wire() {
+        use std::os::unix::net::UnixStream;
+        use zbus::blocking::connection::Builder;
+
+        let calls = Arc::new(Mutex::new(vec![]));
+        let (client_socket, server_socket) = UnixStream::pair().unwrap();
+
+        // 握手要两边同时进行，服务端放到另一个线程里建
+        let server_calls = calls.clone();
+        let server = thread::spawn(move || {
+            Builder::async_io_unix_stream(server_socket)
+                .server(zbus::Guid::generate())?
+                .p2p()
+                .serve_at(
+                    NOTIFICATIONS_PATH,
+                    FakeServer {
+                        calls: server_calls,
+                    },
+                )?
+                .build()
+        });
+        let client = Builder::async_io_unix_stream(client_socket)
+            .p2p()
+            .build()
+            .unwrap();
+        let _server = server.join().unwrap().unwrap();
+
+        let id = send_notification(&client, "Time is up", "Countdown finished").unwrap();
+        assert_eq!(id, 41);
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        let call = &calls[0];
+        assert_eq!(call.app_name, APP_NAME);
+        assert_eq!(call.replaces_id, 0);
+        assert_eq!(call.app_icon, ICON_NAME);
+        assert_eq!(call.summary, "Time is up");
+        assert_eq!(call.body, "Countdown finished");
+        assert!(call.actions.is_empty());
+        assert_eq!(call.hints.len(), 1);
+        assert_eq!(
+            u8::try_from(&call.hints["urgency"]).unwrap(),
+            URGENCY_CRITICAL
+        );
+        // -1：由通知服务决定多久消失
+        assert_eq!(call.expire_timeout, -1);
+    }
+
+    #[test]
+    fn notification_is_critical() {
+        let hints = notification_hints();
+        assert_eq!(hints.get("urgency"), Some(&Value::U8(URGENCY_CRITICAL)));
+    }
+}
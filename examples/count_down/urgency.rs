@@ -0,0 +1,71 @@
+use fltk::{prelude::*, window::Window};
+
+// 让窗口管理器注意这个窗口：X11 上设置 WM_HINTS 里的 XUrgencyHint，
+// 任务栏 / 工作区指示器会高亮，最小化、在别的工作区时也看得到。
+// fltk 没有封装这个，直接调 Xlib；fltk-bundled 本来就链接了 libX11。
+// Windows / macOS 上什么也不做，靠置顶和闪烁
+
+#[cfg(target_os = "linux")]
+mod xlib {
+    use std::os::raw::{c_int, c_long, c_ulong, c_void};
+
+    pub const X_URGENCY_HINT: c_long = 1 << 8;
+
+    // <X11/Xutil.h> 里的 XWMHints
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct XWMHints {
+        pub flags: c_long,
+        pub input: c_int,
+        pub initial_state: c_int,
+        pub icon_pixmap: c_ulong,
+        pub icon_window: c_ulong,
+        pub icon_x: c_int,
+        pub icon_y: c_int,
+        pub icon_mask: c_ulong,
+        pub window_group: c_ulong,
+    }
+
+    #[link(name = "X11")]
+    extern "C" {
+        pub fn XGetWMHints(display: *mut c_void, window: c_ulong) -> *mut XWMHints;
+        pub fn XSetWMHints(display: *mut c_void, window: c_ulong, hints: *mut XWMHints) -> c_int;
+        pub fn XFree(data: *mut c_void) -> c_int;
+        pub fn XFlush(display: *mut c_void) -> c_int;
+    }
+}
+
+// 到时间时设置，开始下一次计时时清掉；窗口还没显示出来就没有 X11 窗口，什么也不做
+#[cfg(target_os = "linux")]
+pub fn set_urgent(window: &Window, urgent: bool) {
+    use std::os::raw::{c_ulong, c_void};
+
+    let display = fltk::app::display();
+    let xid = window.raw_handle() as c_ulong;
+    if display.is_null() || xid == 0 {
+        return;
+    }
+
+    // 保留窗口原来的其他 hint，只改 urgency 这一位
+    unsafe {
+        let existing = xlib::XGetWMHints(display, xid);
+        let mut hints = if existing.is_null() {
+            xlib::XWMHints::default()
+        } else {
+            let hints = std::ptr::read(existing);
+            xlib::XFree(existing as *mut c_void);
+            hints
+        };
+
+        if urgent {
+            hints.flags |= xlib::X_URGENCY_HINT;
+        } else {
+            hints.flags &= !xlib::X_URGENCY_HINT;
+        }
+        xlib::XSetWMHints(display, xid, &mut hints);
+        xlib::XFlush(display);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_urgent(_window: &Window, _urgent: bool) {}
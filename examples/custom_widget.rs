@@ -2,12 +2,12 @@ use std::ops::{Deref, DerefMut};
 
 use fltk::{
     app,
-    button,
-    enums::{self, Align, Color, Event, FrameType},
+    enums::{self, Align, Color, Event},
     group,
     prelude::*,
     window,
 };
+use hello_fltk::{theme, widgets::StyledButton};
 
 // 2025-02-03
 
@@ -21,7 +21,7 @@ use fltk::{
 // 看似 Button，本质是 Group
 struct MyButton {
     grp: group::Group,
-    btn: button::Button,
+    btn: StyledButton,
 }
 
 impl MyButton {
@@ -31,9 +31,10 @@ impl MyButton {
         grp.set_color(Color::from_u32(0xb3e5fc));
         grp.set_align(Align::Center);
 
-        let mut btn = button::Button::new(grp.x() + 420, grp.y() + 35, 18, 18, "X");
-        btn.set_frame(FrameType::RFlatBox);
+        let mut btn = StyledButton::round(18, "X");
+        btn.set_pos(grp.x() + 420, grp.y() + 35);
         btn.set_color(Color::from_u32(0xf49da9));
+        btn.set_tooltip("Close");
         grp.end();
 
         btn.set_callback(move |b| {
@@ -69,6 +70,7 @@ impl DerefMut for MyButton {
 
 fn main() {
     let app = app::App::default();
+    theme::apply_saved();
     app::set_visible_focus(false);
 
     let mut win = window::Window::default().with_size(500, 400);
@@ -0,0 +1,106 @@
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use hello_fltk::pricing::PRICING_PATH;
+
+// 计价接口的 mock，用来试 simple 里的 Pricing 面板，不用连真的服务：
+//   cargo run --example pricing_mock -- 127.0.0.1:8002 500
+// 第二个参数是每个请求故意等多少毫秒；面板的 Base URL 填 http://127.0.0.1:8002
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:8002".to_string());
+    let delay = Duration::from_millis(args.next().and_then(|d| d.parse().ok()).unwrap_or(0));
+
+    let listener = TcpListener::bind(&address).expect("could not bind the mock address");
+    println!("Pricing mock listening on http://{}", address);
+
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, delay) {
+                eprintln!("{}", e);
+            }
+        });
+    }
+}
+
+fn handle(stream: TcpStream, delay: Duration) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    println!("{}", request_line.trim());
+
+    thread::sleep(delay);
+
+    let (status, reply) = if !request_line.starts_with(&format!("POST {}", PRICING_PATH)) {
+        (
+            "404 Not Found",
+            serde_json::json!({ "code": 404, "message": "not found" }),
+        )
+    } else {
+        match serde_json::from_slice::<serde_json::Value>(&body) {
+            Ok(request) => ("200 OK", price(&request)),
+            Err(e) => (
+                "400 Bad Request",
+                serde_json::json!({ "code": 400, "message": e.to_string() }),
+            ),
+        }
+    };
+
+    let reply = reply.to_string();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reply.len(),
+        reply
+    )
+}
+
+// 每个 SO 和模板的组合给一个假价格
+fn price(request: &serde_json::Value) -> serde_json::Value {
+    let ids = |key: &str| -> Vec<u64> {
+        request[key]
+            .as_array()
+            .map(|list| list.iter().filter_map(|v| v.as_u64()).collect())
+            .unwrap_or_default()
+    };
+
+    let mut data = vec![];
+    for so_head_uuid in ids("soHeadUuidList") {
+        for template_id in ids("bcmTmplIdList") {
+            data.push(serde_json::json!({
+                "soHeadUuid": so_head_uuid,
+                "bcmTmplId": template_id,
+                "amount": (so_head_uuid % 1000) as f64 + template_id as f64 / 100.0,
+            }));
+        }
+    }
+
+    serde_json::json!({
+        "code": 200,
+        "message": "success",
+        "companyCode": request["companyCode"],
+        "operator": request["operator"],
+        "data": data,
+    })
+}
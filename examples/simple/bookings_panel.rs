@@ -0,0 +1,119 @@
+use fltk::{
+    app::Sender, browser::HoldBrowser, button::Button, enums::Align, frame::Frame, prelude::*,
+    window::Window,
+};
+
+use crate::flights::{Booking, Inventory};
+use crate::{Message, WIDGET_HEIGHT, WIDGET_PADDING, WIDGET_WIDTH};
+
+// 已经订的票，单独一个窗口：查看、退票
+
+const PANEL_WIDTH: i32 = 640;
+const PANEL_HEIGHT: i32 = 300;
+
+pub struct BookingsPanel {
+    window: Window,
+    browser: HoldBrowser,
+    count_frame: Frame,
+    cancel_button: Button,
+    // 列表的第 n 行 (从 1 开始) 对应 references[n - 1]
+    references: Vec<String>,
+}
+
+impl BookingsPanel {
+    // 创建时不显示，点 "Bookings..." 才打开
+    pub fn new(sender: Sender<Message>) -> Self {
+        let mut window = Window::default()
+            .with_size(PANEL_WIDTH, PANEL_HEIGHT)
+            .with_label("Bookings");
+
+        let mut browser = HoldBrowser::default()
+            .with_pos(WIDGET_PADDING, WIDGET_PADDING)
+            .with_size(
+                PANEL_WIDTH - WIDGET_PADDING * 2,
+                PANEL_HEIGHT - WIDGET_HEIGHT - WIDGET_PADDING * 3,
+            );
+        browser.set_column_char('\t');
+        browser.set_column_widths(&[80, 270, 270]);
+        browser.emit(sender, Message::BookingSelect);
+
+        let count_frame = Frame::default()
+            .with_size(
+                PANEL_WIDTH - (WIDGET_WIDTH * 2 + WIDGET_PADDING * 4),
+                WIDGET_HEIGHT,
+            )
+            .below_of(&browser, WIDGET_PADDING)
+            .with_align(Align::Inside | Align::Left);
+
+        let mut cancel_button = Button::default()
+            .with_size(WIDGET_WIDTH * 2, WIDGET_HEIGHT)
+            .right_of(&count_frame, WIDGET_PADDING)
+            .with_label("Cancel booking");
+        cancel_button.emit(sender, Message::BookingCancel);
+        cancel_button.deactivate();
+
+        window.end();
+
+        Self {
+            window,
+            browser,
+            count_frame,
+            cancel_button,
+            references: vec![],
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.window.show();
+    }
+
+    // 订票、退票之后重新填列表，原来选中的还在的话继续选中
+    pub fn refresh(&mut self, bookings: &[Booking], inventory: &Inventory) {
+        let selected = self.selected_reference();
+
+        self.browser.clear();
+        self.browser.add("@bReference\t@bOutbound\t@bReturn");
+        self.references.clear();
+
+        let mut bookings: Vec<&Booking> = bookings.iter().collect();
+        bookings.sort_by(|a, b| a.outbound.date.cmp(&b.outbound.date));
+        for booking in bookings {
+            let inbound = booking
+                .inbound
+                .as_ref()
+                .map(|leg| inventory.describe(leg))
+                .unwrap_or_else(|| "-".to_string());
+            self.browser.add(&format!(
+                "@.{}\t@.{}\t@.{}",
+                booking.reference,
+                inventory.describe(&booking.outbound),
+                inbound
+            ));
+            self.references.push(booking.reference.clone());
+        }
+
+        if let Some(row) = selected.and_then(|r| self.references.iter().position(|v| *v == r)) {
+            self.browser.select(row as i32 + 2);
+        }
+        self.count_frame
+            .set_label(&format!("{} booking(s)", self.references.len()));
+        self.update_buttons();
+    }
+
+    pub fn selected_reference(&self) -> Option<String> {
+        // 第 1 行是列名
+        let row = self.browser.value();
+        if row < 2 {
+            return None;
+        }
+        self.references.get(row as usize - 2).cloned()
+    }
+
+    pub fn update_buttons(&mut self) {
+        if self.selected_reference().is_some() {
+            self.cancel_button.activate();
+        } else {
+            self.cancel_button.deactivate();
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use fltk::{
+    app, draw,
+    enums::{Align, Color, Event, Font, FrameType, Key},
+    frame::Frame,
+    prelude::*,
+    widget::Widget,
+    window::Window,
+};
+
+// 日历弹窗：按月显示，方向键移动，超出 min / max 的日期不能选
+
+const CELL_WIDTH: i32 = 32;
+const CELL_HEIGHT: i32 = 24;
+const NAV_HEIGHT: i32 = 28;
+const GRID_ROWS: i32 = 6;
+const WEEKDAY_NAMES: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+// 日历的状态，不依赖 fltk
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarState {
+    // 正在显示的月份，总是那个月的 1 号
+    pub month: NaiveDate,
+    // 键盘选中的日期
+    pub cursor: NaiveDate,
+    pub min: Option<NaiveDate>,
+    pub max: Option<NaiveDate>,
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+impl CalendarState {
+    pub fn new(initial: NaiveDate, min: Option<NaiveDate>, max: Option<NaiveDate>) -> Self {
+        let mut state = Self {
+            month: first_of_month(initial),
+            cursor: initial,
+            min,
+            max,
+        };
+        state.set_cursor(initial);
+        state
+    }
+
+    pub fn is_enabled(&self, date: NaiveDate) -> bool {
+        self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+    }
+
+    // 6 行 x 7 列，从 1 号所在那周的周一开始
+    pub fn grid(&self) -> Vec<NaiveDate> {
+        let offset = self.month.weekday().num_days_from_monday() as i64;
+        let start = self.month - Duration::days(offset);
+        (0..GRID_ROWS as i64 * 7)
+            .map(|i| start + Duration::days(i))
+            .collect()
+    }
+
+    // 光标移到 date，限制在 min / max 之间，月份跟着光标走
+    pub fn set_cursor(&mut self, date: NaiveDate) {
+        let mut date = date;
+        if let Some(min) = self.min {
+            date = date.max(min);
+        }
+        if let Some(max) = self.max {
+            date = date.min(max);
+        }
+        self.cursor = date;
+        self.month = first_of_month(date);
+    }
+
+    pub fn move_days(&mut self, days: i64) {
+        self.set_cursor(self.cursor + Duration::days(days));
+    }
+
+    // 31 号往后翻一个月，落在下个月的最后一天
+    pub fn move_months(&mut self, months: i32) {
+        let moved = if months >= 0 {
+            self.cursor.checked_add_months(Months::new(months as u32))
+        } else {
+            self.cursor
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+        };
+        if let Some(date) = moved {
+            self.set_cursor(date);
+        }
+    }
+}
+
+// 在 anchor 下方弹出日历，选中日期后关闭；Esc 或 Cancel 返回 None
+pub fn pick_date<W: WidgetExt>(
+    anchor: &W,
+    initial: NaiveDate,
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    let state = Rc::new(RefCell::new(CalendarState::new(initial, min, max)));
+    let picked = Rc::new(Cell::new(None));
+
+    let width = CELL_WIDTH * 7;
+    let height = NAV_HEIGHT * 2 + CELL_HEIGHT * (GRID_ROWS + 1);
+
+    // anchor 的坐标是相对窗口的，弹窗要用屏幕坐标
+    let (x, y) = anchor
+        .window()
+        .map(|w| (w.x() + anchor.x(), w.y() + anchor.y() + anchor.h()))
+        .unwrap_or((anchor.x(), anchor.y() + anchor.h()));
+
+    let mut win = Window::new(x, y, width, height, None);
+    win.set_border(false);
+    win.set_frame(FrameType::BorderBox);
+
+    let mut prev_button = fltk::button::Button::new(0, 0, NAV_HEIGHT, NAV_HEIGHT, "@<");
+    let mut month_frame = Frame::new(NAV_HEIGHT, 0, width - NAV_HEIGHT * 2, NAV_HEIGHT, None);
+    month_frame.set_label_font(Font::HelveticaBold);
+    let mut next_button =
+        fltk::button::Button::new(width - NAV_HEIGHT, 0, NAV_HEIGHT, NAV_HEIGHT, "@>");
+
+    let mut grid = Widget::new(0, NAV_HEIGHT, width, CELL_HEIGHT * (GRID_ROWS + 1), None);
+
+    let bottom = height - NAV_HEIGHT;
+    let mut today_button = fltk::button::Button::new(0, bottom, width / 2, NAV_HEIGHT, "Today");
+    let mut cancel_button =
+        fltk::button::Button::new(width / 2, bottom, width - width / 2, NAV_HEIGHT, "Cancel");
+
+    win.end();
+    win.make_modal(true);
+
+    let mut refresh = {
+        let state = state.clone();
+        let mut month_frame = month_frame.clone();
+        let mut grid = grid.clone();
+        move || {
+            month_frame.set_label(&state.borrow().month.format("%B %Y").to_string());
+            grid.redraw();
+        }
+    };
+    refresh();
+
+    grid.draw({
+        let state = state.clone();
+        move |g| draw_grid(g, &state.borrow())
+    });
+
+    grid.handle({
+        let state = state.clone();
+        let picked = picked.clone();
+        let mut win = win.clone();
+        let mut refresh = refresh.clone();
+        move |g, ev| match ev {
+            Event::Focus | Event::Unfocus => true,
+            Event::Push => {
+                let _ = g.take_focus();
+                let col = (app::event_x() - g.x()) / CELL_WIDTH;
+                let row = (app::event_y() - g.y()) / CELL_HEIGHT - 1;
+                if !(0..7).contains(&col) || !(0..GRID_ROWS).contains(&row) {
+                    return true;
+                }
+
+                let date = state.borrow().grid()[(row * 7 + col) as usize];
+                if state.borrow().is_enabled(date) {
+                    picked.set(Some(date));
+                    win.hide();
+                }
+                true
+            }
+            Event::KeyDown => {
+                let mut state = state.borrow_mut();
+                match app::event_key() {
+                    Key::Left => state.move_days(-1),
+                    Key::Right => state.move_days(1),
+                    Key::Up => state.move_days(-7),
+                    Key::Down => state.move_days(7),
+                    Key::PageUp => state.move_months(-1),
+                    Key::PageDown => state.move_months(1),
+                    Key::Home => {
+                        let first = first_of_month(state.cursor);
+                        state.set_cursor(first);
+                    }
+                    Key::End => {
+                        let last =
+                            first_of_month(state.cursor) + Months::new(1) - Duration::days(1);
+                        state.set_cursor(last);
+                    }
+                    Key::Enter | Key::KPEnter => {
+                        picked.set(Some(state.cursor));
+                        win.hide();
+                        return true;
+                    }
+                    Key::Escape => {
+                        win.hide();
+                        return true;
+                    }
+                    _ => return false,
+                }
+                drop(state);
+                refresh();
+                true
+            }
+            _ => false,
+        }
+    });
+
+    // 翻页按钮只换显示的月份，光标跟着移到那个月
+    prev_button.set_callback({
+        let state = state.clone();
+        let mut refresh = refresh.clone();
+        move |_| {
+            state.borrow_mut().move_months(-1);
+            refresh();
+        }
+    });
+    next_button.set_callback({
+        let state = state.clone();
+        let mut refresh = refresh.clone();
+        move |_| {
+            state.borrow_mut().move_months(1);
+            refresh();
+        }
+    });
+    today_button.set_callback({
+        let state = state.clone();
+        let mut refresh = refresh.clone();
+        move |_| {
+            state
+                .borrow_mut()
+                .set_cursor(Local::now().naive_local().date());
+            refresh();
+        }
+    });
+    cancel_button.set_callback({
+        let mut win = win.clone();
+        move |_| win.hide()
+    });
+
+    win.show();
+    let _ = grid.take_focus();
+    while win.shown() {
+        app::wait();
+    }
+
+    picked.get()
+}
+
+fn draw_grid(g: &Widget, state: &CalendarState) {
+    let today = Local::now().naive_local().date();
+
+    draw::draw_rect_fill(g.x(), g.y(), g.w(), g.h(), Color::BackGround2);
+
+    draw::set_font(Font::HelveticaBold, app::font_size() - 2);
+    draw::set_draw_color(Color::Inactive);
+    for (col, name) in WEEKDAY_NAMES.iter().enumerate() {
+        let x = g.x() + col as i32 * CELL_WIDTH;
+        draw::draw_text2(name, x, g.y(), CELL_WIDTH, CELL_HEIGHT, Align::Center);
+    }
+
+    for (index, date) in state.grid().into_iter().enumerate() {
+        let x = g.x() + (index as i32 % 7) * CELL_WIDTH;
+        let y = g.y() + (index as i32 / 7 + 1) * CELL_HEIGHT;
+        let is_cursor = date == state.cursor;
+
+        if is_cursor {
+            draw::draw_rect_fill(
+                x + 1,
+                y + 1,
+                CELL_WIDTH - 2,
+                CELL_HEIGHT - 2,
+                g.selection_color(),
+            );
+        }
+
+        // 今天加粗；不能选的、不是这个月的日期用灰色
+        let font = if date == today {
+            Font::HelveticaBold
+        } else {
+            Font::Helvetica
+        };
+        let color = if is_cursor {
+            Color::White
+        } else if !state.is_enabled(date) {
+            Color::Light1
+        } else if date.month() != state.month.month() {
+            Color::Inactive
+        } else {
+            Color::Foreground
+        };
+        draw::set_font(font, app::font_size());
+        draw::set_draw_color(color);
+        draw::draw_text2(
+            &date.day().to_string(),
+            x,
+            y,
+            CELL_WIDTH,
+            CELL_HEIGHT,
+            Align::Center,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn month_follows_the_cursor() {
+        let state = CalendarState::new(date(2025, 3, 17), None, None);
+        assert_eq!(state.month, date(2025, 3, 1));
+        assert_eq!(state.cursor, date(2025, 3, 17));
+    }
+
+    #[test]
+    fn month_navigation_crosses_years() {
+        let mut state = CalendarState::new(date(2025, 12, 15), None, None);
+        state.move_months(1);
+        assert_eq!(state.cursor, date(2026, 1, 15));
+        assert_eq!(state.month, date(2026, 1, 1));
+
+        state.move_months(-2);
+        assert_eq!(state.cursor, date(2025, 11, 15));
+
+        state.move_months(-11);
+        assert_eq!(state.cursor, date(2024, 12, 15));
+    }
+
+    #[test]
+    fn day_navigation_crosses_years() {
+        let mut state = CalendarState::new(date(2025, 12, 31), None, None);
+        state.move_days(1);
+        assert_eq!(state.cursor, date(2026, 1, 1));
+        assert_eq!(state.month, date(2026, 1, 1));
+        state.move_days(-7);
+        assert_eq!(state.month, date(2025, 12, 1));
+    }
+
+    #[test]
+    fn end_of_month_clamps_to_february() {
+        let mut state = CalendarState::new(date(2024, 1, 31), None, None);
+        state.move_months(1);
+        // 闰年
+        assert_eq!(state.cursor, date(2024, 2, 29));
+
+        let mut state = CalendarState::new(date(2025, 1, 31), None, None);
+        state.move_months(1);
+        assert_eq!(state.cursor, date(2025, 2, 28));
+    }
+
+    #[test]
+    fn leap_february_grid() {
+        let feb = |year| -> Vec<NaiveDate> {
+            CalendarState::new(date(year, 2, 10), None, None)
+                .grid()
+                .into_iter()
+                .filter(|d| d.month() == 2)
+                .collect()
+        };
+        assert_eq!(feb(2024).len(), 29);
+        assert_eq!(feb(2025).len(), 28);
+        // 整百年不是闰年，整四百年是
+        assert_eq!(feb(2100).len(), 28);
+        assert_eq!(feb(2000).len(), 29);
+    }
+
+    #[test]
+    fn grid_starts_on_the_monday_before_the_first() {
+        // 2025-10-01 是星期三，前面补两天
+        let grid = CalendarState::new(date(2025, 10, 20), None, None).grid();
+        assert_eq!(grid.len(), 42);
+        assert_eq!(grid[0], date(2025, 9, 29));
+        assert_eq!(grid[2], date(2025, 10, 1));
+        assert_eq!(grid[41], date(2025, 11, 9));
+
+        // 1 号就是星期一，不用补
+        let grid = CalendarState::new(date(2025, 9, 20), None, None).grid();
+        assert_eq!(grid[0], date(2025, 9, 1));
+
+        // 1 号是星期日，补六天
+        let grid = CalendarState::new(date(2025, 6, 20), None, None).grid();
+        assert_eq!(grid[0], date(2025, 5, 26));
+        assert_eq!(grid[6], date(2025, 6, 1));
+    }
+
+    #[test]
+    fn cursor_stays_between_min_and_max() {
+        let min = date(2025, 3, 10);
+        let max = date(2025, 4, 5);
+        let mut state = CalendarState::new(date(2025, 1, 1), Some(min), Some(max));
+        assert_eq!(state.cursor, min);
+        assert_eq!(state.month, date(2025, 3, 1));
+
+        state.move_months(3);
+        assert_eq!(state.cursor, max);
+        state.move_days(-100);
+        assert_eq!(state.cursor, min);
+
+        assert!(!state.is_enabled(date(2025, 3, 9)));
+        assert!(state.is_enabled(min));
+        assert!(state.is_enabled(max));
+        assert!(!state.is_enabled(date(2025, 4, 6)));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+// MyCustomButton 的计数和订阅；不依赖 fltk
+
+pub type ClickHandler = Box<dyn FnMut(i32)>;
+
+pub struct ClickCounter {
+    clicks: Cell<i32>,
+    // 每次点击加多少
+    step: Cell<i32>,
+    // 计数变化时通知，点击和 reset 都算
+    handlers: RefCell<Vec<ClickHandler>>,
+    // 通知的过程中 handler 又改了计数：先排队，这一轮通知完再发
+    pending: RefCell<VecDeque<i32>>,
+    notifying: Cell<bool>,
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self {
+            clicks: Cell::new(0),
+            step: Cell::new(1),
+            handlers: RefCell::new(vec![]),
+            pending: RefCell::new(VecDeque::new()),
+            notifying: Cell::new(false),
+        }
+    }
+}
+
+impl ClickCounter {
+    pub fn clicks(&self) -> i32 {
+        self.clicks.get()
+    }
+
+    pub fn step(&self) -> i32 {
+        self.step.get()
+    }
+
+    pub fn set_step(&self, step: i32) {
+        self.step.set(step);
+    }
+
+    pub fn click(&self) {
+        self.set(self.clicks() + self.step());
+    }
+
+    // 计数归零，也会通知订阅者
+    pub fn reset(&self) {
+        self.set(0);
+    }
+
+    // handler 里可以再调用 on_change / reset / set_step
+    pub fn on_change(&self, handler: impl FnMut(i32) + 'static) {
+        self.handlers.borrow_mut().push(Box::new(handler));
+    }
+
+    fn set(&self, clicks: i32) {
+        self.clicks.set(clicks);
+        self.pending.borrow_mut().push_back(clicks);
+        if self.notifying.replace(true) {
+            return;
+        }
+
+        loop {
+            let next = self.pending.borrow_mut().pop_front();
+            let Some(clicks) = next else {
+                break;
+            };
+
+            // 调用 handler 时不能借着 handlers，先拿出来，通知完再放回去
+            let mut handlers = self.handlers.take();
+            for handler in handlers.iter_mut() {
+                handler(clicks);
+            }
+            // 通知期间新订阅的排在原来的后面
+            let added = self.handlers.replace(handlers);
+            self.handlers.borrow_mut().extend(added);
+        }
+        self.notifying.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    // 记下每次通知的值
+    fn record(counter: &ClickCounter) -> Rc<RefCell<Vec<i32>>> {
+        let seen = Rc::new(RefCell::new(vec![]));
+        counter.on_change({
+            let seen = seen.clone();
+            move |clicks| seen.borrow_mut().push(clicks)
+        });
+        seen
+    }
+
+    #[test]
+    fn clicks_add_the_step() {
+        let counter = ClickCounter::default();
+        let seen = record(&counter);
+
+        counter.click();
+        counter.click();
+        counter.set_step(-5);
+        counter.click();
+
+        assert_eq!(counter.clicks(), -3);
+        assert_eq!(*seen.borrow(), vec![1, 2, -3]);
+    }
+
+    #[test]
+    fn reset_notifies_every_subscriber() {
+        let counter = ClickCounter::default();
+        let first = record(&counter);
+        counter.click();
+        let second = record(&counter);
+        counter.reset();
+
+        assert_eq!(counter.clicks(), 0);
+        assert_eq!(*first.borrow(), vec![1, 0]);
+        assert_eq!(*second.borrow(), vec![0]);
+    }
+
+    #[test]
+    fn handler_may_reset_the_counter() {
+        let counter = Rc::new(ClickCounter::default());
+        counter.on_change({
+            let counter = Rc::downgrade(&counter);
+            move |clicks| {
+                if clicks >= 3 {
+                    counter.upgrade().unwrap().reset();
+                }
+            }
+        });
+        let seen = record(&counter);
+
+        for _ in 0..4 {
+            counter.click();
+        }
+
+        assert_eq!(counter.clicks(), 1);
+        // 每个订阅者都按顺序看到 3 和紧接着的 0
+        assert_eq!(*seen.borrow(), vec![1, 2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn handler_may_subscribe_and_change_the_step() {
+        let counter = Rc::new(ClickCounter::default());
+        let late = Rc::new(RefCell::new(vec![]));
+        counter.on_change({
+            let counter = Rc::downgrade(&counter);
+            let late = late.clone();
+            move |clicks| {
+                let counter = counter.upgrade().unwrap();
+                counter.set_step(10);
+                if clicks == 1 {
+                    let late = late.clone();
+                    counter.on_change(move |clicks| late.borrow_mut().push(clicks));
+                }
+            }
+        });
+
+        counter.click();
+        counter.click();
+
+        assert_eq!(counter.clicks(), 11);
+        // 订阅之后的变化才收得到
+        assert_eq!(*late.borrow(), vec![11]);
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::{Store, StoreError};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Person {
+    pub id: u64,
+    pub name: String,
+    pub surname: String,
+}
+
+impl Person {
+    // 列表里显示的格式
+    pub fn display_name(&self) -> String {
+        format!("{}, {}", self.surname, self.name)
+    }
+}
+
+#[derive(Debug)]
+pub enum CrudError {
+    // 写文件失败
+    Store(StoreError),
+    // 要改、要删的记录已经不在了
+    NotFound(u64),
+}
+
+impl fmt::Display for CrudError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrudError::Store(e) => write!(f, "{}", e),
+            CrudError::NotFound(id) => write!(f, "record {} does not exist", id),
+        }
+    }
+}
+
+impl From<StoreError> for CrudError {
+    fn from(e: StoreError) -> Self {
+        CrudError::Store(e)
+    }
+}
+
+// 所有的增删改都经过这里：先写文件，成功后才改内存里的数据
+pub struct PersonRepository {
+    store: Store<Person>,
+    people: Vec<Person>,
+    next_id: u64,
+}
+
+impl PersonRepository {
+    pub fn new(store: Store<Person>, people: Vec<Person>) -> Self {
+        let next_id = people.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+        Self {
+            store,
+            people,
+            next_id,
+        }
+    }
+
+    pub fn store(&self) -> &Store<Person> {
+        &self.store
+    }
+
+    pub fn all(&self) -> &[Person] {
+        &self.people
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Person> {
+        self.people.iter().find(|p| p.id == id)
+    }
+
+    pub fn create(&mut self, name: &str, surname: &str) -> Result<u64, CrudError> {
+        let id = self.next_id;
+        let mut next = self.people.clone();
+        next.push(Person {
+            id,
+            name: name.to_string(),
+            surname: surname.to_string(),
+        });
+
+        self.commit(next)?;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    pub fn update(&mut self, id: u64, name: &str, surname: &str) -> Result<(), CrudError> {
+        let mut next = self.people.clone();
+        let person = next
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or(CrudError::NotFound(id))?;
+        person.name = name.to_string();
+        person.surname = surname.to_string();
+
+        self.commit(next)
+    }
+
+    pub fn delete(&mut self, id: u64) -> Result<Person, CrudError> {
+        let mut next = self.people.clone();
+        let index = next
+            .iter()
+            .position(|p| p.id == id)
+            .ok_or(CrudError::NotFound(id))?;
+        let person = next.remove(index);
+
+        self.commit(next)?;
+        Ok(person)
+    }
+
+    // 导入：一次写文件，要么全部成功，要么都不变
+    pub fn create_many(&mut self, records: &[(String, String)]) -> Result<Vec<Person>, CrudError> {
+        let created: Vec<Person> = records
+            .iter()
+            .zip(self.next_id..)
+            .map(|((name, surname), id)| Person {
+                id,
+                name: name.clone(),
+                surname: surname.clone(),
+            })
+            .collect();
+
+        let mut next = self.people.clone();
+        next.extend(created.iter().cloned());
+
+        self.commit(next)?;
+        self.next_id += created.len() as u64;
+        Ok(created)
+    }
+
+    pub fn delete_many(&mut self, ids: &[u64]) -> Result<(), CrudError> {
+        let mut next = self.people.clone();
+        next.retain(|p| !ids.contains(&p.id));
+        self.commit(next)
+    }
+
+    // 撤销删除 / 重做创建：按原来的 id 放回去
+    pub fn restore(&mut self, person: Person) -> Result<(), CrudError> {
+        self.restore_many(vec![person])
+    }
+
+    pub fn restore_many(&mut self, people: Vec<Person>) -> Result<(), CrudError> {
+        let max_id = people.iter().map(|p| p.id).max().unwrap_or(0);
+        let mut next = self.people.clone();
+        next.retain(|p| people.iter().all(|r| r.id != p.id));
+        next.extend(people);
+
+        self.commit(next)?;
+        self.next_id = self.next_id.max(max_id + 1);
+        Ok(())
+    }
+
+    fn commit(&mut self, next: Vec<Person>) -> Result<(), CrudError> {
+        self.store.save(&next)?;
+        self.people = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("crud-test-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn repository(name: &str) -> PersonRepository {
+        PersonRepository::new(Store::new(temp_path(name)), vec![])
+    }
+
+    // 重新打开同一个文件，和程序启动时一样
+    fn reopen(repository: &PersonRepository) -> PersonRepository {
+        let store = Store::new(repository.store().path());
+        let people = store.load_people().unwrap().unwrap_or_default();
+        PersonRepository::new(store, people)
+    }
+
+    fn person(id: u64, name: &str, surname: &str) -> Person {
+        Person {
+            id,
+            name: name.to_string(),
+            surname: surname.to_string(),
+        }
+    }
+
+    fn records(names: &[(&str, &str)]) -> Vec<(String, String)> {
+        names
+            .iter()
+            .map(|(name, surname)| (name.to_string(), surname.to_string()))
+            .collect()
+    }
+
+    fn ids(repository: &PersonRepository) -> Vec<u64> {
+        repository.all().iter().map(|p| p.id).collect()
+    }
+
+    #[test]
+    fn ids_continue_after_the_largest_existing_one() {
+        let people = vec![person(7, "Alan", "Turing"), person(3, "Grace", "Hopper")];
+        let mut repository = PersonRepository::new(Store::new(temp_path("next-id")), people);
+        assert_eq!(repository.create("Ada", "Lovelace").unwrap(), 8);
+        assert_eq!(repository.create("Alonzo", "Church").unwrap(), 9);
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn create_is_saved_and_survives_reopen() {
+        let mut repository = repository("create");
+        let id = repository.create("Alan", "Turing").unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(repository.get(id), Some(&person(1, "Alan", "Turing")));
+        assert_eq!(repository.get(id).unwrap().display_name(), "Turing, Alan");
+
+        let reopened = reopen(&repository);
+        assert_eq!(reopened.all(), repository.all());
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn update_changes_only_that_record() {
+        let mut repository = repository("update");
+        let alan = repository.create("Alan", "Turing").unwrap();
+        let grace = repository.create("Grace", "Hopper").unwrap();
+
+        repository.update(alan, "Alonzo", "Church").unwrap();
+        assert_eq!(
+            repository.get(alan),
+            Some(&person(alan, "Alonzo", "Church"))
+        );
+        assert_eq!(
+            repository.get(grace),
+            Some(&person(grace, "Grace", "Hopper"))
+        );
+        assert_eq!(reopen(&repository).all(), repository.all());
+
+        match repository.update(99, "Nobody", "Here") {
+            Err(CrudError::NotFound(id)) => assert_eq!(id, 99),
+            other => panic!("expected not found, got {:?}", other),
+        }
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn delete_returns_the_removed_record() {
+        let mut repository = repository("delete");
+        let alan = repository.create("Alan", "Turing").unwrap();
+        let grace = repository.create("Grace", "Hopper").unwrap();
+
+        assert_eq!(
+            repository.delete(alan).unwrap(),
+            person(alan, "Alan", "Turing")
+        );
+        assert_eq!(ids(&repository), vec![grace]);
+        assert_eq!(ids(&reopen(&repository)), vec![grace]);
+
+        match repository.delete(alan) {
+            Err(CrudError::NotFound(id)) => assert_eq!(id, alan),
+            other => panic!("expected not found, got {:?}", other),
+        }
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn deleted_ids_are_reused_only_after_reopen() {
+        let mut repository = repository("reuse");
+        repository.create("Alan", "Turing").unwrap();
+        let grace = repository.create("Grace", "Hopper").unwrap();
+
+        // 这次运行里不重复，撤销删除时才能按原来的 id 放回去
+        repository.delete(grace).unwrap();
+        assert_eq!(repository.create("Ada", "Lovelace").unwrap(), 3);
+
+        // 下次启动从文件里最大的 id 往后数，最后删掉的 id 会再用
+        repository.delete(3).unwrap();
+        let mut reopened = reopen(&repository);
+        assert_eq!(reopened.create("Alonzo", "Church").unwrap(), 2);
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn create_many_and_delete_many() {
+        let mut repository = repository("many");
+        repository.create("Alan", "Turing").unwrap();
+
+        let created = repository
+            .create_many(&records(&[("Grace", "Hopper"), ("Ada", "Lovelace")]))
+            .unwrap();
+        assert_eq!(
+            created,
+            vec![person(2, "Grace", "Hopper"), person(3, "Ada", "Lovelace")]
+        );
+        assert_eq!(repository.create("Alonzo", "Church").unwrap(), 4);
+        assert_eq!(ids(&reopen(&repository)), vec![1, 2, 3, 4]);
+
+        // 不存在的 id 忽略
+        repository.delete_many(&[1, 3, 99]).unwrap();
+        assert_eq!(ids(&repository), vec![2, 4]);
+        assert_eq!(ids(&reopen(&repository)), vec![2, 4]);
+
+        assert!(repository.create_many(&[]).unwrap().is_empty());
+        assert_eq!(repository.create("Edsger", "Dijkstra").unwrap(), 5);
+        let _ = fs::remove_file(repository.store().path());
+    }
+
+    #[test]
+    fn restore_many_keeps_ids_and_moves_next_id() {
+        let mut repository = repository("restore");
+        repository.create("Alan", "Turing").unwrap();
+
+        // 放回去的记录替换同 id 的，不会重复
+        repository
+            .restore_many(vec![
+                person(1, "Alonzo", "Church"),
+                person(10, "Grace", "Hopper"),
+            ])
+            .unwrap();
+        assert_eq!(ids(&repository), vec![1, 10]);
+        assert_eq!(repository.get(1), Some(&person(1, "Alonzo", "Church")));
+        assert_eq!(repository.create("Ada", "Lovelace").unwrap(), 11);
+
+        // id 比现在的小，不会让 next_id 倒退
+        repository.restore(person(5, "Edsger", "Dijkstra")).unwrap();
+        assert_eq!(repository.create("Barbara", "Liskov").unwrap(), 12);
+        assert_eq!(reopen(&repository).all(), repository.all());
+        let _ = fs::remove_file(repository.store().path());
+    }
+}
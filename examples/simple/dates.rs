@@ -0,0 +1,305 @@
+use std::fmt;
+
+use chrono::{Duration, Months, NaiveDate};
+
+// 订票的日期输入：接受常见的写法，显示成选定的格式；不依赖 fltk
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DateFormat {
+    // 2026-10-17
+    Iso,
+    // 17/10/2026
+    DayMonthYear,
+    // 10/17/2026
+    MonthDayYear,
+    // Oct 17 2026
+    Long,
+}
+
+impl DateFormat {
+    pub const ALL: [DateFormat; 4] = [
+        DateFormat::Iso,
+        DateFormat::DayMonthYear,
+        DateFormat::MonthDayYear,
+        DateFormat::Long,
+    ];
+
+    fn pattern(self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::DayMonthYear => "%d/%m/%Y",
+            DateFormat::MonthDayYear => "%m/%d/%Y",
+            DateFormat::Long => "%b %d %Y",
+        }
+    }
+
+    // 下拉框里显示的名字
+    pub fn label(self) -> &'static str {
+        match self {
+            DateFormat::Iso => "YYYY-MM-DD",
+            DateFormat::DayMonthYear => "DD/MM/YYYY",
+            DateFormat::MonthDayYear => "MM/DD/YYYY",
+            DateFormat::Long => "Mon DD YYYY",
+        }
+    }
+
+    pub fn format(self, date: NaiveDate) -> String {
+        date.format(self.pattern()).to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateError {
+    Empty,
+    // 看不懂的写法
+    Unrecognized(String),
+    // 写法对，但日期不存在，例如 31/02/2026
+    NoSuchDate(String),
+    // +3d 之类的加出界了
+    OutOfRange(String),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::Empty => write!(f, "Enter a date"),
+            DateError::Unrecognized(text) => write!(
+                f,
+                "\"{}\" is not a date. Try 2026-10-17, 17/10/2026, Oct 17 2026, today, tomorrow or +3d",
+                text
+            ),
+            DateError::NoSuchDate(text) => write!(f, "\"{}\" does not exist in the calendar", text),
+            DateError::OutOfRange(text) => write!(f, "\"{}\" is too far away", text),
+        }
+    }
+}
+
+// 日、月不会弄混的写法；斜杠的写法按显示格式决定先日还是先月
+const UNAMBIGUOUS_PATTERNS: [&str; 8] = [
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%d.%m.%Y",
+    "%b %d %Y",
+    "%b %d, %Y",
+    "%d %b %Y",
+    "%B %d %Y",
+    "%B %d, %Y",
+];
+
+pub fn parse_date(
+    text: &str,
+    today: NaiveDate,
+    format: DateFormat,
+) -> Result<NaiveDate, DateError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DateError::Empty);
+    }
+
+    let lower = text.to_lowercase();
+    match lower.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Duration::days(1)),
+        "yesterday" => return Ok(today - Duration::days(1)),
+        _ => (),
+    }
+
+    if let Some(result) = parse_relative(&lower, today) {
+        return result.ok_or_else(|| DateError::OutOfRange(text.to_string()));
+    }
+
+    // MM/DD/YYYY 的地区先按月份试，其他地区先按日期试
+    let slash_patterns = match format {
+        DateFormat::MonthDayYear => ["%m/%d/%Y", "%d/%m/%Y"],
+        _ => ["%d/%m/%Y", "%m/%d/%Y"],
+    };
+
+    let mut looks_like_date = false;
+    for pattern in UNAMBIGUOUS_PATTERNS.iter().chain(&slash_patterns) {
+        match NaiveDate::parse_from_str(text, pattern) {
+            Ok(date) => return Ok(date),
+            // 数字都对上了，只是日期不存在
+            Err(e) if e.kind() == chrono::format::ParseErrorKind::OutOfRange => {
+                looks_like_date = true
+            }
+            Err(_) => (),
+        }
+    }
+
+    if looks_like_date {
+        Err(DateError::NoSuchDate(text.to_string()))
+    } else {
+        Err(DateError::Unrecognized(text.to_string()))
+    }
+}
+
+// +3d、-1w、+2m；不是这种写法返回 None，加出界返回 Some(None)
+fn parse_relative(text: &str, today: NaiveDate) -> Option<Option<NaiveDate>> {
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let unit = text.chars().last()?;
+    if text.len() < 1 + unit.len_utf8() + 1 {
+        return None;
+    }
+    let amount: i64 = text[1..text.len() - unit.len_utf8()].trim().parse().ok()?;
+    let amount = sign * amount;
+
+    Some(match unit {
+        'd' => Duration::try_days(amount).and_then(|d| today.checked_add_signed(d)),
+        'w' => Duration::try_weeks(amount).and_then(|d| today.checked_add_signed(d)),
+        'm' => {
+            let months = u32::try_from(amount.unsigned_abs()).ok().map(Months::new);
+            if amount >= 0 {
+                months.and_then(|m| today.checked_add_months(m))
+            } else {
+                months.and_then(|m| today.checked_sub_months(m))
+            }
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        date(2025, 10, 17)
+    }
+
+    fn parse(text: &str) -> Result<NaiveDate, DateError> {
+        parse_date(text, today(), DateFormat::Iso)
+    }
+
+    #[test]
+    fn every_written_format_is_accepted() {
+        let expected = Ok(date(2026, 3, 7));
+        for text in [
+            "2026-03-07",
+            "2026/03/07",
+            "07.03.2026",
+            "Mar 07 2026",
+            "Mar 7, 2026",
+            "7 Mar 2026",
+            "March 7 2026",
+            "march 7, 2026",
+            "07/03/2026",
+            "  2026-3-7  ",
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn slash_order_follows_the_display_format() {
+        assert_eq!(
+            parse_date("07/03/2026", today(), DateFormat::DayMonthYear),
+            Ok(date(2026, 3, 7))
+        );
+        assert_eq!(
+            parse_date("07/03/2026", today(), DateFormat::MonthDayYear),
+            Ok(date(2026, 7, 3))
+        );
+        // 只有一种读法合法时，不管显示格式
+        assert_eq!(
+            parse_date("25/12/2026", today(), DateFormat::MonthDayYear),
+            Ok(date(2026, 12, 25))
+        );
+        assert_eq!(
+            parse_date("12/25/2026", today(), DateFormat::DayMonthYear),
+            Ok(date(2026, 12, 25))
+        );
+    }
+
+    #[test]
+    fn words_are_relative_to_today() {
+        assert_eq!(parse("today"), Ok(today()));
+        assert_eq!(parse(" Today "), Ok(today()));
+        assert_eq!(parse("TOMORROW"), Ok(date(2025, 10, 18)));
+        assert_eq!(parse("yesterday"), Ok(date(2025, 10, 16)));
+    }
+
+    #[test]
+    fn relative_offsets() {
+        assert_eq!(parse("+3d"), Ok(date(2025, 10, 20)));
+        assert_eq!(parse("-1d"), Ok(date(2025, 10, 16)));
+        assert_eq!(parse("+2w"), Ok(date(2025, 10, 31)));
+        assert_eq!(parse("+ 1 w"), Ok(date(2025, 10, 24)));
+        assert_eq!(parse("+3m"), Ok(date(2026, 1, 17)));
+        assert_eq!(parse("-10m"), Ok(date(2024, 12, 17)));
+        assert_eq!(parse("+0d"), Ok(today()));
+        assert_eq!(
+            parse_relative("+1m", date(2026, 1, 31)),
+            Some(Some(date(2026, 2, 28)))
+        );
+    }
+
+    #[test]
+    fn relative_offsets_out_of_range() {
+        assert_eq!(
+            parse("+99999999d"),
+            Err(DateError::OutOfRange("+99999999d".to_string()))
+        );
+        assert_eq!(
+            parse("-99999999999m"),
+            Err(DateError::OutOfRange("-99999999999m".to_string()))
+        );
+    }
+
+    #[test]
+    fn not_relative_offsets() {
+        assert_eq!(parse_relative("3d", today()), None);
+        assert_eq!(parse_relative("+d", today()), None);
+        assert_eq!(parse_relative("+3y", today()), None);
+        assert_eq!(parse_relative("+xd", today()), None);
+        assert_eq!(parse_relative("+", today()), None);
+    }
+
+    #[test]
+    fn impossible_dates_are_not_unrecognized() {
+        assert_eq!(
+            parse("2025-02-30"),
+            Err(DateError::NoSuchDate("2025-02-30".to_string()))
+        );
+        assert_eq!(
+            parse("31/04/2026"),
+            Err(DateError::NoSuchDate("31/04/2026".to_string()))
+        );
+        assert_eq!(parse("2024-02-29"), Ok(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn garbage_and_empty_input() {
+        assert_eq!(parse("   "), Err(DateError::Empty));
+        assert_eq!(
+            parse("next friday"),
+            Err(DateError::Unrecognized("next friday".to_string()))
+        );
+        assert_eq!(
+            parse("Oct 1x 2026"),
+            Err(DateError::Unrecognized("Oct 1x 2026".to_string()))
+        );
+    }
+
+    #[test]
+    fn normalised_output() {
+        let date = date(2026, 3, 7);
+        let formatted: Vec<String> = DateFormat::ALL.iter().map(|f| f.format(date)).collect();
+        assert_eq!(
+            formatted,
+            vec!["2026-03-07", "07/03/2026", "03/07/2026", "Mar 07 2026"]
+        );
+
+        // 格式化后的文本能原样读回来
+        for format in DateFormat::ALL {
+            assert_eq!(parse_date(&format.format(date), today(), format), Ok(date));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Instant;
+
+use fltk::{
+    browser::Browser,
+    button::{Button, CheckButton},
+    enums::Event,
+    group::Group,
+    prelude::*,
+    window::Window,
+};
+
+use crate::{WIDGET_HEIGHT, WIDGET_PADDING, WIDGET_WIDTH};
+
+// gallery 的事件记录：每个 widget 收到的事件、触发的回调和当时的值，单独一个窗口
+
+const LOG_WIDTH: i32 = 480;
+const LOG_HEIGHT: i32 = 360;
+// 太多了就把最早的删掉
+const MAX_LINES: i32 = 1000;
+
+#[derive(Clone)]
+pub struct EventLog {
+    window: Window,
+    browser: Browser,
+    raw_check: CheckButton,
+    started: Instant,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventLog {
+    // 可以在别的窗口还没 end() 的时候创建，不会变成它的子窗口
+    pub fn new() -> Self {
+        let parent = Group::try_current();
+        Group::set_current(None::<&Group>);
+
+        let mut window = Window::default()
+            .with_size(LOG_WIDTH, LOG_HEIGHT)
+            .with_label("Gallery events");
+
+        let mut browser = Browser::default()
+            .with_pos(WIDGET_PADDING, WIDGET_PADDING)
+            .with_size(
+                LOG_WIDTH - WIDGET_PADDING * 2,
+                LOG_HEIGHT - WIDGET_HEIGHT - WIDGET_PADDING * 3,
+            );
+        browser.set_column_char('\t');
+        browser.set_column_widths(&[70, 110, 90]);
+
+        let mut raw_check = CheckButton::default()
+            .with_size(WIDGET_WIDTH * 2, WIDGET_HEIGHT)
+            .below_of(&browser, WIDGET_PADDING)
+            .with_label("Raw events");
+        raw_check.set_checked(true);
+        raw_check.set_tooltip("Also log Push, Release, Focus, KeyDown ... not only callbacks");
+
+        let mut clear_button = Button::default()
+            .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+            .with_pos(LOG_WIDTH - WIDGET_PADDING - WIDGET_WIDTH, raw_check.y())
+            .with_label("Clear");
+
+        window.end();
+        window.resizable(&browser);
+
+        clear_button.set_callback({
+            let mut browser = browser.clone();
+            move |_| browser.clear()
+        });
+
+        if let Some(parent) = parent {
+            parent.begin();
+        }
+
+        Self {
+            window,
+            browser,
+            raw_check,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.window.show();
+    }
+
+    // 一行：时间、widget、发生了什么、值
+    pub fn log(&mut self, source: &str, what: &str, value: &str) {
+        let seconds = self.started.elapsed().as_secs_f64();
+        // @. 之后不再解析 @ 格式，值里面有 @ 也原样显示
+        self.browser.add(&format!(
+            "@.{:.3}\t@.{}\t@.{}\t@.{}",
+            seconds, source, what, value
+        ));
+        while self.browser.size() > MAX_LINES {
+            self.browser.remove(1);
+        }
+        self.browser.bottom_line(self.browser.size());
+    }
+
+    // 记下 widget 收到的原始事件；不拦截，事件照常交给 widget 处理
+    pub fn watch<W: WidgetExt + WidgetBase>(&self, name: &'static str, widget: &mut W) {
+        let mut log = self.clone();
+        widget.handle(move |_, ev| {
+            // Move 每动一下鼠标就来一次，太吵
+            if log.raw_check.is_checked() && !matches!(ev, Event::Move | Event::NoEvent) {
+                log.log(name, &format!("{:?}", ev), "");
+            }
+            false
+        });
+    }
+}
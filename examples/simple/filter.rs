@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use crate::crud::Person;
+
+// CRUD 列表的搜索、排序；不依赖 fltk，方便单独测试
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MatchMode {
+    // 任意字段包含搜索的文本
+    Substring,
+    // 搜索的字符按顺序出现即可，例如 "bbg" 匹配 "Babbage"
+    Fuzzy,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SortColumn {
+    Surname,
+    Name,
+    // 创建的先后顺序
+    Id,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub text: String,
+    pub mode: MatchMode,
+    pub column: SortColumn,
+    pub direction: SortDirection,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            mode: MatchMode::Substring,
+            column: SortColumn::Surname,
+            direction: SortDirection::Ascending,
+        }
+    }
+}
+
+// 一条匹配的记录，以及哪些字段匹配上了，用来高亮
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<'a> {
+    pub person: &'a Person,
+    pub surname_hit: bool,
+    pub name_hit: bool,
+}
+
+fn is_match(field: &str, needle: &str, mode: MatchMode) -> bool {
+    let field = field.to_lowercase();
+    match mode {
+        MatchMode::Substring => field.contains(needle),
+        MatchMode::Fuzzy => {
+            let mut chars = field.chars();
+            needle.chars().all(|n| chars.any(|c| c == n))
+        }
+    }
+}
+
+fn compare(a: &Person, b: &Person, column: SortColumn) -> Ordering {
+    let by_text = |x: &str, y: &str| x.to_lowercase().cmp(&y.to_lowercase());
+    match column {
+        SortColumn::Surname => {
+            by_text(&a.surname, &b.surname).then_with(|| by_text(&a.name, &b.name))
+        }
+        SortColumn::Name => by_text(&a.name, &b.name).then_with(|| by_text(&a.surname, &b.surname)),
+        SortColumn::Id => Ordering::Equal,
+    }
+    .then(a.id.cmp(&b.id))
+}
+
+// 搜索文本为空时，全部记录都显示，不高亮
+pub fn filter_people<'a>(people: &'a [Person], query: &Query) -> Vec<Match<'a>> {
+    let needle = query.text.trim().to_lowercase();
+
+    let mut matches: Vec<Match> = people
+        .iter()
+        .filter_map(|person| {
+            if needle.is_empty() {
+                return Some(Match {
+                    person,
+                    surname_hit: false,
+                    name_hit: false,
+                });
+            }
+
+            let surname_hit = is_match(&person.surname, &needle, query.mode);
+            let name_hit = is_match(&person.name, &needle, query.mode);
+
+            // "Babbage, Ch" 这种跨字段的搜索，两个字段都算匹配
+            let cross_hit =
+                !surname_hit && !name_hit && is_match(&person.display_name(), &needle, query.mode);
+
+            (surname_hit || name_hit || cross_hit).then_some(Match {
+                person,
+                surname_hit: surname_hit || cross_hit,
+                name_hit: name_hit || cross_hit,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        let ordering = compare(a.person, b.person, query.column);
+        match query.direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    });
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(id: u64, name: &str, surname: &str) -> Person {
+        Person {
+            id,
+            name: name.to_string(),
+            surname: surname.to_string(),
+        }
+    }
+
+    fn people() -> Vec<Person> {
+        vec![
+            person(1, "Hans", "Emil"),
+            person(2, "Max", "Mustermann"),
+            person(3, "Roman", "Tisch"),
+            person(4, "Charles", "Babbage"),
+        ]
+    }
+
+    fn query(text: &str, mode: MatchMode) -> Query {
+        Query {
+            text: text.to_string(),
+            mode,
+            ..Query::default()
+        }
+    }
+
+    fn ids(matches: &[Match]) -> Vec<u64> {
+        matches.iter().map(|m| m.person.id).collect()
+    }
+
+    #[test]
+    fn empty_text_shows_everyone_without_highlight() {
+        let people = people();
+        let matches = filter_people(&people, &query("  ", MatchMode::Substring));
+        // 默认按姓排序
+        assert_eq!(ids(&matches), vec![4, 1, 2, 3]);
+        assert!(matches.iter().all(|m| !m.surname_hit && !m.name_hit));
+    }
+
+    #[test]
+    fn substring_needs_contiguous_text() {
+        let people = people();
+        assert_eq!(
+            ids(&filter_people(&people, &query("man", MatchMode::Substring))),
+            vec![2, 3]
+        );
+        assert!(filter_people(&people, &query("bbg", MatchMode::Substring)).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_needs_characters_in_order() {
+        let people = people();
+        assert_eq!(
+            ids(&filter_people(&people, &query("bbg", MatchMode::Fuzzy))),
+            vec![4]
+        );
+        // 顺序反了不算
+        assert!(filter_people(&people, &query("gbb", MatchMode::Fuzzy)).is_empty());
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let people = people();
+        for text in ["EMIL", "emil", "eMiL"] {
+            assert_eq!(
+                ids(&filter_people(&people, &query(text, MatchMode::Substring))),
+                vec![1]
+            );
+        }
+        assert_eq!(
+            ids(&filter_people(&people, &query("MSTR", MatchMode::Fuzzy))),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn sort_by_column_and_direction() {
+        let people = people();
+        let mut q = query("", MatchMode::Substring);
+
+        q.column = SortColumn::Name;
+        assert_eq!(ids(&filter_people(&people, &q)), vec![4, 1, 2, 3]);
+        q.direction = SortDirection::Descending;
+        assert_eq!(ids(&filter_people(&people, &q)), vec![3, 2, 1, 4]);
+
+        q.column = SortColumn::Id;
+        assert_eq!(ids(&filter_people(&people, &q)), vec![4, 3, 2, 1]);
+        q.direction = SortDirection::Ascending;
+        assert_eq!(ids(&filter_people(&people, &q)), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn same_surname_sorts_by_name_then_id() {
+        let people = vec![
+            person(1, "Zoe", "Smith"),
+            person(2, "adam", "smith"),
+            person(3, "Adam", "Smith"),
+        ];
+        let matches = filter_people(&people, &Query::default());
+        assert_eq!(ids(&matches), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn highlight_only_the_fields_that_match() {
+        let people = people();
+
+        let matches = filter_people(&people, &query("ans", MatchMode::Substring));
+        assert_eq!(ids(&matches), vec![1]);
+        assert!(!matches[0].surname_hit);
+        assert!(matches[0].name_hit);
+
+        let matches = filter_people(&people, &query("tisch", MatchMode::Substring));
+        assert!(matches[0].surname_hit);
+        assert!(!matches[0].name_hit);
+    }
+
+    #[test]
+    fn search_across_fields_highlights_both() {
+        let people = people();
+        let matches = filter_people(&people, &query("Babbage, Ch", MatchMode::Substring));
+        assert_eq!(ids(&matches), vec![4]);
+        assert!(matches[0].surname_hit);
+        assert!(matches[0].name_hit);
+    }
+}
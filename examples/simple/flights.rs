@@ -0,0 +1,594 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::Path;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::store::{Store, StoreError};
+
+// 航班库存和订票记录；不依赖 fltk
+
+// 航线、时刻表、座位数，手工维护的 JSON
+pub const INVENTORY_FILE_PATH: &str = "./assets/flights.json";
+// 订票记录，JSON lines：一行一张票
+pub const BOOKINGS_FILE_PATH: &str = "./data/bookings.jsonl";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// 订票号用的字符，去掉了容易看错的 0 O 1 I
+const REFERENCE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const REFERENCE_LENGTH: usize = 6;
+
+#[derive(Debug)]
+pub enum FlightError {
+    Io(io::Error),
+    Corrupt {
+        line: usize,
+        message: String,
+    },
+    SoldOut {
+        code: String,
+        date: NaiveDate,
+    },
+    NoFlight {
+        code: String,
+        date: NaiveDate,
+    },
+    InboundBeforeOutbound {
+        outbound: NaiveDate,
+        inbound: NaiveDate,
+    },
+    NotFound(String),
+}
+
+impl fmt::Display for FlightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlightError::Io(e) => write!(f, "{}", e),
+            FlightError::Corrupt { line, message } => write!(f, "line {}: {}", line, message),
+            FlightError::SoldOut { code, date } => write!(f, "{} on {} is sold out", code, date),
+            FlightError::NoFlight { code, date } => {
+                write!(f, "{} does not fly on {}", code, date)
+            }
+            FlightError::InboundBeforeOutbound { outbound, inbound } => {
+                write!(
+                    f,
+                    "return on {} is before departure on {}",
+                    inbound, outbound
+                )
+            }
+            FlightError::NotFound(reference) => write!(f, "booking {} does not exist", reference),
+        }
+    }
+}
+
+impl From<io::Error> for FlightError {
+    fn from(e: io::Error) -> Self {
+        FlightError::Io(e)
+    }
+}
+
+// 订票记录的读写由 Store 负责，出错时转换过来
+impl From<StoreError> for FlightError {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::Io(e) => FlightError::Io(e),
+            StoreError::Corrupt { line, message } => FlightError::Corrupt { line, message },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Route {
+    pub from: String,
+    pub to: String,
+}
+
+impl Route {
+    // 回程
+    pub fn reverse(&self) -> Route {
+        Route {
+            from: self.to.clone(),
+            to: self.from.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.from, self.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Flight {
+    pub code: String,
+    pub from: String,
+    pub to: String,
+    // "08:30"，只用来显示
+    pub departure: String,
+    // 每周哪几天飞，例如 ["Mon", "Fri"]；不写就是每天都飞
+    #[serde(default)]
+    pub days: Vec<String>,
+    pub seats: u32,
+}
+
+impl Flight {
+    pub fn route(&self) -> Route {
+        Route {
+            from: self.from.clone(),
+            to: self.to.clone(),
+        }
+    }
+
+    pub fn flies_on(&self, date: NaiveDate) -> bool {
+        let weekday = WEEKDAYS[date.weekday().num_days_from_monday() as usize];
+        self.days.is_empty() || self.days.iter().any(|d| d.eq_ignore_ascii_case(weekday))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    flights: Vec<Flight>,
+}
+
+impl Inventory {
+    pub fn load(path: impl AsRef<Path>) -> Result<Inventory, FlightError> {
+        let text = fs::read_to_string(path)?;
+        let flights = serde_json::from_str(&text).map_err(|e| FlightError::Corrupt {
+            line: e.line(),
+            message: e.to_string(),
+        })?;
+        Ok(Inventory { flights })
+    }
+
+    pub fn get(&self, code: &str) -> Option<&Flight> {
+        self.flights.iter().find(|f| f.code == code)
+    }
+
+    // 例如 "RS101 2026-10-19 08:30 HKG - TPE"
+    pub fn describe(&self, leg: &Leg) -> String {
+        match self.get(&leg.code) {
+            Some(flight) => format!(
+                "{} {} {} {}",
+                leg.code,
+                leg.date,
+                flight.departure,
+                flight.route()
+            ),
+            None => format!("{} {}", leg.code, leg.date),
+        }
+    }
+
+    // 所有航线，按字母排序，去重
+    pub fn routes(&self) -> Vec<Route> {
+        let mut routes: Vec<Route> = self.flights.iter().map(|f| f.route()).collect();
+        routes.sort();
+        routes.dedup();
+        routes
+    }
+
+    // 某天某条航线上的航班，按起飞时间排序
+    pub fn flights_on(&self, route: &Route, date: NaiveDate) -> Vec<&Flight> {
+        let mut flights: Vec<&Flight> = self
+            .flights
+            .iter()
+            .filter(|f| f.route() == *route && f.flies_on(date))
+            .collect();
+        flights.sort_by(|a, b| a.departure.cmp(&b.departure));
+        flights
+    }
+}
+
+// 一段航程：哪个航班，哪一天
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Leg {
+    pub code: String,
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Booking {
+    pub reference: String,
+    pub outbound: Leg,
+    #[serde(default)]
+    pub inbound: Option<Leg>,
+    pub booked_at: NaiveDateTime,
+}
+
+impl Booking {
+    pub fn legs(&self) -> impl Iterator<Item = &Leg> {
+        std::iter::once(&self.outbound).chain(self.inbound.as_ref())
+    }
+}
+
+// 所有的订票、退票都经过这里：先写文件，成功后才改内存里的数据
+pub struct BookingStore {
+    store: Store<Booking>,
+    bookings: Vec<Booking>,
+}
+
+impl BookingStore {
+    pub fn new(store: Store<Booking>, bookings: Vec<Booking>) -> BookingStore {
+        BookingStore { store, bookings }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.store.path()
+    }
+
+    pub fn all(&self) -> &[Booking] {
+        &self.bookings
+    }
+
+    // 库存里的座位数减去已经订出去的
+    pub fn seats_left(&self, flight: &Flight, date: NaiveDate) -> u32 {
+        let booked = self
+            .bookings
+            .iter()
+            .flat_map(|b| b.legs())
+            .filter(|leg| leg.code == flight.code && leg.date == date)
+            .count() as u32;
+        flight.seats.saturating_sub(booked)
+    }
+
+    pub fn book(
+        &mut self,
+        inventory: &Inventory,
+        outbound: Leg,
+        inbound: Option<Leg>,
+        now: NaiveDateTime,
+    ) -> Result<Booking, FlightError> {
+        // 界面上也查过，这里再查一次，不让错的记录写进文件
+        if let Some(inbound) = &inbound {
+            if inbound.date < outbound.date {
+                return Err(FlightError::InboundBeforeOutbound {
+                    outbound: outbound.date,
+                    inbound: inbound.date,
+                });
+            }
+        }
+
+        let legs: Vec<&Leg> = std::iter::once(&outbound).chain(inbound.as_ref()).collect();
+        for leg in &legs {
+            let flight = inventory
+                .get(&leg.code)
+                .filter(|f| f.flies_on(leg.date))
+                .ok_or_else(|| FlightError::NoFlight {
+                    code: leg.code.clone(),
+                    date: leg.date,
+                })?;
+            // 去程回程是同一天的同一班，要占两个座位
+            let needed = legs.iter().filter(|l| **l == *leg).count() as u32;
+            if self.seats_left(flight, leg.date) < needed {
+                return Err(FlightError::SoldOut {
+                    code: leg.code.clone(),
+                    date: leg.date,
+                });
+            }
+        }
+
+        let booking = Booking {
+            reference: self.new_reference(),
+            outbound,
+            inbound,
+            booked_at: now,
+        };
+
+        let mut next = self.bookings.clone();
+        next.push(booking.clone());
+        self.commit(next)?;
+        Ok(booking)
+    }
+
+    pub fn cancel(&mut self, reference: &str) -> Result<Booking, FlightError> {
+        let mut next = self.bookings.clone();
+        let index = next
+            .iter()
+            .position(|b| b.reference == reference)
+            .ok_or_else(|| FlightError::NotFound(reference.to_string()))?;
+        let booking = next.remove(index);
+
+        self.commit(next)?;
+        Ok(booking)
+    }
+
+    // 随机 6 位，和已有的不重复
+    fn new_reference(&self) -> String {
+        loop {
+            let mut seed = RandomState::new().build_hasher().finish();
+            let reference: String = (0..REFERENCE_LENGTH)
+                .map(|_| {
+                    let c = REFERENCE_CHARS[(seed % REFERENCE_CHARS.len() as u64) as usize];
+                    seed /= REFERENCE_CHARS.len() as u64;
+                    c as char
+                })
+                .collect();
+
+            if self.bookings.iter().all(|b| b.reference != reference) {
+                return reference;
+            }
+        }
+    }
+
+    fn commit(&mut self, next: Vec<Booking>) -> Result<(), FlightError> {
+        self.store.save(&next)?;
+        self.bookings = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "flights-test-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn now() -> NaiveDateTime {
+        date(2026, 10, 18).and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    fn flight(code: &str, from: &str, to: &str, days: &[&str], seats: u32) -> Flight {
+        Flight {
+            code: code.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            departure: "08:30".to_string(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            seats,
+        }
+    }
+
+    fn inventory() -> Inventory {
+        Inventory {
+            flights: vec![
+                flight("RS103", "HKG", "TPE", &["Mon", "fri"], 2),
+                flight("RS101", "HKG", "TPE", &[], 1),
+                flight("RS102", "TPE", "HKG", &[], 4),
+                flight("RS201", "HKG", "NRT", &["Tue"], 3),
+            ],
+        }
+    }
+
+    fn leg(code: &str, date: NaiveDate) -> Leg {
+        Leg {
+            code: code.to_string(),
+            date,
+        }
+    }
+
+    fn bookings(name: &str) -> BookingStore {
+        BookingStore::new(Store::new(temp_path(name)), vec![])
+    }
+
+    #[test]
+    fn flies_on_listed_days_or_every_day() {
+        let inventory = inventory();
+        let monday = date(2026, 10, 19);
+        let tuesday = date(2026, 10, 20);
+        let friday = date(2026, 10, 23);
+
+        let rs103 = inventory.get("RS103").unwrap();
+        assert!(rs103.flies_on(monday));
+        assert!(rs103.flies_on(friday));
+        assert!(!rs103.flies_on(tuesday));
+        assert!(inventory.get("RS101").unwrap().flies_on(tuesday));
+    }
+
+    #[test]
+    fn routes_are_sorted_and_unique() {
+        let routes: Vec<String> = inventory().routes().iter().map(|r| r.to_string()).collect();
+        assert_eq!(routes, vec!["HKG - NRT", "HKG - TPE", "TPE - HKG"]);
+    }
+
+    #[test]
+    fn seats_left_counts_both_legs_of_bookings() {
+        let inventory = inventory();
+        let mut bookings = bookings("seats-left");
+        let monday = date(2026, 10, 19);
+        bookings
+            .book(
+                &inventory,
+                leg("RS103", monday),
+                Some(leg("RS102", monday)),
+                now(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            bookings.seats_left(inventory.get("RS103").unwrap(), monday),
+            1
+        );
+        assert_eq!(
+            bookings.seats_left(inventory.get("RS102").unwrap(), monday),
+            3
+        );
+        // 别的日子不受影响
+        let tuesday = date(2026, 10, 20);
+        assert_eq!(
+            bookings.seats_left(inventory.get("RS102").unwrap(), tuesday),
+            4
+        );
+        let _ = fs::remove_file(bookings.path());
+    }
+
+    #[test]
+    fn book_is_saved_and_stops_at_capacity() {
+        let inventory = inventory();
+        let mut bookings = bookings("capacity");
+        let monday = date(2026, 10, 19);
+
+        bookings
+            .book(&inventory, leg("RS103", monday), None, now())
+            .unwrap();
+        bookings
+            .book(&inventory, leg("RS103", monday), None, now())
+            .unwrap();
+        match bookings.book(&inventory, leg("RS103", monday), None, now()) {
+            Err(FlightError::SoldOut { code, date }) => {
+                assert_eq!(code, "RS103");
+                assert_eq!(date, monday);
+            }
+            other => panic!("expected sold out, got {:?}", other),
+        }
+
+        let saved = Store::<Booking>::new(bookings.path()).load().unwrap();
+        assert_eq!(saved.as_deref(), Some(bookings.all()));
+        assert_eq!(bookings.all().len(), 2);
+        let _ = fs::remove_file(bookings.path());
+    }
+
+    #[test]
+    fn book_rejects_a_day_without_the_flight() {
+        let inventory = inventory();
+        let mut bookings = bookings("no-flight");
+        let tuesday = date(2026, 10, 20);
+
+        for code in ["RS103", "XX999"] {
+            match bookings.book(&inventory, leg(code, tuesday), None, now()) {
+                Err(FlightError::NoFlight { code: c, date }) => {
+                    assert_eq!(c, code);
+                    assert_eq!(date, tuesday);
+                }
+                other => panic!("expected no flight, got {:?}", other),
+            }
+        }
+        assert!(bookings.all().is_empty());
+        assert!(!bookings.path().exists());
+    }
+
+    #[test]
+    fn book_rejects_inbound_before_outbound() {
+        let inventory = inventory();
+        let mut bookings = bookings("inbound-before");
+        let monday = date(2026, 10, 19);
+        let sunday = date(2026, 10, 18);
+
+        match bookings.book(
+            &inventory,
+            leg("RS101", monday),
+            Some(leg("RS102", sunday)),
+            now(),
+        ) {
+            Err(FlightError::InboundBeforeOutbound { outbound, inbound }) => {
+                assert_eq!(outbound, monday);
+                assert_eq!(inbound, sunday);
+            }
+            other => panic!("expected inbound before outbound, got {:?}", other),
+        }
+        assert!(bookings.all().is_empty());
+
+        // 同一天来回可以
+        bookings
+            .book(
+                &inventory,
+                leg("RS101", monday),
+                Some(leg("RS102", monday)),
+                now(),
+            )
+            .unwrap();
+        let _ = fs::remove_file(bookings.path());
+    }
+
+    #[test]
+    fn same_flight_on_both_legs_needs_two_seats() {
+        let inventory = inventory();
+        let mut bookings = bookings("same-flight");
+        let monday = date(2026, 10, 19);
+
+        // RS101 只有一个座位，不能来回都坐它
+        match bookings.book(
+            &inventory,
+            leg("RS101", monday),
+            Some(leg("RS101", monday)),
+            now(),
+        ) {
+            Err(FlightError::SoldOut { code, .. }) => assert_eq!(code, "RS101"),
+            other => panic!("expected sold out, got {:?}", other),
+        }
+        assert!(bookings.all().is_empty());
+
+        // RS103 有两个座位，刚好够
+        bookings
+            .book(
+                &inventory,
+                leg("RS103", monday),
+                Some(leg("RS103", monday)),
+                now(),
+            )
+            .unwrap();
+        assert_eq!(
+            bookings.seats_left(inventory.get("RS103").unwrap(), monday),
+            0
+        );
+        let _ = fs::remove_file(bookings.path());
+    }
+
+    #[test]
+    fn cancel_removes_the_booking() {
+        let inventory = inventory();
+        let mut bookings = bookings("cancel");
+        let monday = date(2026, 10, 19);
+        let booking = bookings
+            .book(&inventory, leg("RS101", monday), None, now())
+            .unwrap();
+
+        match bookings.cancel("NOSUCH") {
+            Err(FlightError::NotFound(reference)) => assert_eq!(reference, "NOSUCH"),
+            other => panic!("expected not found, got {:?}", other),
+        }
+        assert_eq!(bookings.all().len(), 1);
+
+        assert_eq!(bookings.cancel(&booking.reference).unwrap(), booking);
+        assert!(bookings.all().is_empty());
+        assert_eq!(
+            bookings.seats_left(inventory.get("RS101").unwrap(), monday),
+            1
+        );
+        let _ = fs::remove_file(bookings.path());
+    }
+
+    #[test]
+    fn references_are_unique() {
+        let inventory = Inventory {
+            flights: vec![flight("RS102", "TPE", "HKG", &[], 200)],
+        };
+        let mut bookings = bookings("references");
+        let monday = date(2026, 10, 19);
+        for _ in 0..100 {
+            bookings
+                .book(&inventory, leg("RS102", monday), None, now())
+                .unwrap();
+        }
+
+        let mut references: Vec<&str> = bookings
+            .all()
+            .iter()
+            .map(|b| b.reference.as_str())
+            .collect();
+        assert!(references.iter().all(
+            |r| r.len() == REFERENCE_LENGTH && r.bytes().all(|c| REFERENCE_CHARS.contains(&c))
+        ));
+        references.sort();
+        references.dedup();
+        assert_eq!(references.len(), 100);
+        let _ = fs::remove_file(bookings.path());
+    }
+}
@@ -0,0 +1,399 @@
+use std::{cell::RefCell, rc::Rc};
+
+use fltk::{
+    app,
+    button::Button,
+    dialog::{color_chooser, ColorMode},
+    enums::{Align, CallbackTrigger, Color, Event, FrameType},
+    frame::Frame,
+    group::{Scroll, ScrollType},
+    input::Input,
+    misc::Spinner,
+    prelude::*,
+    window::Window,
+};
+
+use crate::{WIDGET_HEIGHT, WIDGET_PADDING, WIDGET_WIDTH};
+
+// 所有 FrameType 的预览：可以滚动、按名字或编号过滤，预览的颜色、大小、文字可以改，
+// 点一下把 FrameType::Xxx 复制到剪贴板
+
+const BROWSER_WIDTH: i32 = 1000;
+const BROWSER_HEIGHT: i32 = 800;
+const CAPTION_HEIGHT: i32 = 20;
+const CELL_SIZE_DEFAULT: f64 = 75.0;
+const CELL_SIZE_MIN: f64 = 30.0;
+const CELL_SIZE_MAX: f64 = 200.0;
+const PREVIEW_COLOR: u32 = 0x7FFFD4;
+// 这个上限只是保险，见 enumerate_until
+const FRAME_TYPE_LIMIT: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct FrameInfo {
+    pub index: usize,
+    pub name: String,
+    pub frame: FrameType,
+}
+
+// 按编号列出所有 FrameType，不用手工维护个数；开销不小，算一次传给用到的地方
+// FreeBoxType 开始是留给用户自定义的，没有东西可画；名字只用来显示
+pub fn frame_types() -> Vec<FrameInfo> {
+    enumerate_until(
+        FrameType::by_index,
+        FrameType::FreeBoxType,
+        FRAME_TYPE_LIMIT,
+    )
+    .into_iter()
+    .enumerate()
+    .map(|(index, frame)| FrameInfo {
+        index,
+        name: format!("{:?}", frame),
+        frame,
+    })
+    .collect()
+}
+
+// 从 0 开始按编号取，取到 end 为止；by_index 超出范围时会停在某个值上不动，
+// 所以和前面重复也算到头了。按值比较，不依赖名字
+fn enumerate_until<T: Copy + PartialEq>(
+    by_index: impl Fn(usize) -> T,
+    end: T,
+    limit: usize,
+) -> Vec<T> {
+    let mut values: Vec<T> = vec![];
+    for index in 0..limit {
+        let value = by_index(index);
+        if value == end || values.contains(&value) {
+            break;
+        }
+        values.push(value);
+    }
+    values
+}
+
+// 空格分开的每个词都要匹配：名字里有 (不分大小写)，或者是编号
+pub fn frame_matches(info: &FrameInfo, query: &str) -> bool {
+    let name = info.name.to_lowercase();
+    query.split_whitespace().all(|word| {
+        word.parse::<usize>()
+            .map(|index| index == info.index)
+            .unwrap_or_else(|_| name.contains(&word.to_lowercase()))
+    })
+}
+
+// 第 i 个格子在第几行、第几列
+pub fn grid_cell(i: usize, columns: usize) -> (i32, i32) {
+    let columns = columns.max(1);
+    ((i / columns) as i32, (i % columns) as i32)
+}
+
+struct FrameCell {
+    preview: Frame,
+    caption: Frame,
+}
+
+struct BrowserState {
+    frames: Vec<FrameInfo>,
+    cells: Vec<FrameCell>,
+    query: String,
+    color: Color,
+    size: i32,
+    label: String,
+}
+
+pub fn show_frame_browser(frames: &[FrameInfo]) {
+    let mut win = Window::default()
+        .with_size(BROWSER_WIDTH, BROWSER_HEIGHT)
+        .with_label("Frames")
+        .center_screen();
+
+    let mut filter_input = Input::default()
+        .with_size(WIDGET_WIDTH * 2, WIDGET_HEIGHT)
+        .with_pos(WIDGET_PADDING + WIDGET_WIDTH, WIDGET_PADDING)
+        .with_label("Filter:");
+    filter_input.set_trigger(CallbackTrigger::Changed);
+    filter_input.set_tooltip("Name or index, e.g. \"round\", \"gtk up\" or \"12\"");
+
+    let mut color_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&filter_input, WIDGET_PADDING + WIDGET_WIDTH)
+        .with_label("Color:")
+        .with_align(Align::Left);
+    color_button.set_color(Color::from_u32(PREVIEW_COLOR));
+
+    let mut size_spinner = Spinner::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&color_button, WIDGET_PADDING + WIDGET_WIDTH)
+        .with_label("Size:");
+    size_spinner.set_range(CELL_SIZE_MIN, CELL_SIZE_MAX);
+    size_spinner.set_step(5.0);
+    size_spinner.set_value(CELL_SIZE_DEFAULT);
+
+    let mut label_input = Input::default()
+        .with_size(WIDGET_WIDTH * 2, WIDGET_HEIGHT)
+        .right_of(&size_spinner, WIDGET_PADDING + WIDGET_WIDTH)
+        .with_label("Label:");
+    label_input.set_trigger(CallbackTrigger::Changed);
+
+    let count_frame = Frame::default()
+        .with_size(WIDGET_WIDTH * 3, WIDGET_HEIGHT)
+        .right_of(&label_input, WIDGET_PADDING)
+        .with_align(Align::Inside | Align::Left);
+
+    let top = WIDGET_PADDING * 2 + WIDGET_HEIGHT;
+    let mut scroll = Scroll::new(
+        0,
+        top,
+        BROWSER_WIDTH,
+        BROWSER_HEIGHT - top - WIDGET_HEIGHT - WIDGET_PADDING,
+        None,
+    );
+    scroll.set_type(ScrollType::Vertical);
+
+    let frames = frames.to_vec();
+    let mut cells = vec![];
+    for info in &frames {
+        let mut preview = Frame::default();
+        preview.set_frame(info.frame);
+        let mut caption = Frame::default().with_label(&format!("{}  {}", info.index, info.name));
+        caption.set_label_size(app::font_size() - 2);
+        preview.set_tooltip(&format!("FrameType::{} (click to copy)", info.name));
+        cells.push(FrameCell { preview, caption });
+    }
+    scroll.end();
+
+    let mut status_frame = Frame::default()
+        .with_size(BROWSER_WIDTH - WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .with_pos(
+            WIDGET_PADDING,
+            BROWSER_HEIGHT - WIDGET_HEIGHT - WIDGET_PADDING / 2,
+        )
+        .with_align(Align::Inside | Align::Left)
+        .with_label("Click a frame to copy its FrameType to the clipboard");
+
+    win.end();
+    win.resizable(&scroll);
+
+    let state = Rc::new(RefCell::new(BrowserState {
+        frames,
+        cells,
+        query: String::new(),
+        color: Color::from_u32(PREVIEW_COLOR),
+        size: CELL_SIZE_DEFAULT as i32,
+        label: String::new(),
+    }));
+
+    // 过滤、改大小、窗口改变大小之后重新排格子
+    let relayout = {
+        let state = state.clone();
+        let mut scroll = scroll.clone();
+        let mut count_frame = count_frame.clone();
+        move || {
+            let mut state = state.borrow_mut();
+            let BrowserState {
+                frames,
+                cells,
+                query,
+                color,
+                size,
+                label,
+            } = &mut *state;
+
+            let cell_width = *size + WIDGET_PADDING * 2;
+            let cell_height = *size + CAPTION_HEIGHT + WIDGET_PADDING;
+            let usable = scroll.w() - scroll.scrollbar_size().max(app::scrollbar_size());
+            let columns = (usable / cell_width).max(1) as usize;
+
+            // 子控件的坐标是绝对的，先滚回顶部再排
+            scroll.scroll_to(0, 0);
+
+            let mut shown = 0;
+            for (info, cell) in frames.iter().zip(cells.iter_mut()) {
+                if !frame_matches(info, query) {
+                    cell.preview.hide();
+                    cell.caption.hide();
+                    continue;
+                }
+
+                let (row, col) = grid_cell(shown, columns);
+                let x = scroll.x() + WIDGET_PADDING + col * cell_width;
+                let y = scroll.y() + WIDGET_PADDING + row * cell_height;
+                cell.preview.resize(x, y, *size, *size);
+                cell.preview.set_color(*color);
+                cell.preview.set_label(label);
+                cell.caption
+                    .resize(x - WIDGET_PADDING, y + *size, cell_width, CAPTION_HEIGHT);
+                cell.preview.show();
+                cell.caption.show();
+                shown += 1;
+            }
+
+            count_frame.set_label(&format!("{} of {} frame types", shown, frames.len()));
+            scroll.redraw();
+        }
+    };
+
+    let mut layout = relayout.clone();
+    layout();
+
+    filter_input.set_callback({
+        let state = state.clone();
+        let mut layout = relayout.clone();
+        move |i| {
+            state.borrow_mut().query = i.value();
+            layout();
+        }
+    });
+    color_button.set_callback({
+        let state = state.clone();
+        let mut layout = relayout.clone();
+        move |button| {
+            if let Some((r, g, b)) = color_chooser("Preview color", ColorMode::Rgb) {
+                let color = Color::from_rgb(r, g, b);
+                button.set_color(color);
+                button.redraw();
+                state.borrow_mut().color = color;
+                layout();
+            }
+        }
+    });
+    size_spinner.set_callback({
+        let state = state.clone();
+        let mut layout = relayout.clone();
+        move |s| {
+            state.borrow_mut().size = s.value() as i32;
+            layout();
+        }
+    });
+    label_input.set_callback({
+        let state = state.clone();
+        let mut layout = relayout.clone();
+        move |i| {
+            state.borrow_mut().label = i.value();
+            layout();
+        }
+    });
+    scroll.resize_callback({
+        let mut layout = relayout.clone();
+        move |_, _, _, _, _| layout()
+    });
+
+    // 点格子：复制名字
+    scroll.handle({
+        let state = state.clone();
+        move |_, ev| {
+            if ev != Event::Push {
+                return false;
+            }
+            let (x, y) = app::event_coords();
+            let state = state.borrow();
+            let hit = state.frames.iter().zip(&state.cells).find(|(_, cell)| {
+                let p = &cell.preview;
+                p.visible() && x >= p.x() && x < p.x() + p.w() && y >= p.y() && y < p.y() + p.h()
+            });
+            match hit {
+                Some((info, _)) => {
+                    let text = format!("FrameType::{}", info.name);
+                    app::copy(&text);
+                    status_frame.set_label(&format!("Copied {} (index {})", text, info.index));
+                    true
+                }
+                // 滚动条之类的交给 Scroll 自己处理
+                None => false,
+            }
+        }
+    });
+
+    win.show();
+    win.set_color(Color::White);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(index: usize, name: &str) -> FrameInfo {
+        FrameInfo {
+            index,
+            name: name.to_string(),
+            frame: FrameType::by_index(index),
+        }
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert!(frame_matches(&info(2, "UpBox"), ""));
+        assert!(frame_matches(&info(2, "UpBox"), "   "));
+    }
+
+    #[test]
+    fn query_matches_name_ignoring_case() {
+        let frame = info(20, "GtkRoundUpBox");
+        assert!(frame_matches(&frame, "round"));
+        assert!(frame_matches(&frame, "GTK"));
+        assert!(!frame_matches(&frame, "down"));
+    }
+
+    #[test]
+    fn every_word_must_match() {
+        let frame = info(20, "GtkRoundUpBox");
+        assert!(frame_matches(&frame, "gtk up"));
+        assert!(frame_matches(&frame, "up  gtk"));
+        assert!(!frame_matches(&frame, "gtk down"));
+    }
+
+    #[test]
+    fn numbers_match_the_index_only() {
+        let frame = info(12, "Plastic2UpBox");
+        assert!(frame_matches(&frame, "12"));
+        assert!(frame_matches(&frame, "12 plastic"));
+        assert!(!frame_matches(&frame, "2"));
+        assert!(!frame_matches(&frame, "13"));
+    }
+
+    #[test]
+    fn grid_cells_fill_rows_first() {
+        let cells: Vec<(i32, i32)> = (0..7).map(|i| grid_cell(i, 3)).collect();
+        assert_eq!(
+            cells,
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn grid_has_at_least_one_column() {
+        assert_eq!(grid_cell(0, 0), (0, 0));
+        assert_eq!(grid_cell(4, 0), (4, 0));
+        assert_eq!(grid_cell(4, 1), (4, 0));
+    }
+
+    #[test]
+    fn enumeration_stops_at_end() {
+        let values = enumerate_until(|i| i, 4, 100);
+        assert_eq!(values, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn enumeration_stops_when_index_is_clamped() {
+        // 超出范围时一直返回最后一个，end 永远等不到
+        let values = enumerate_until(|i| i.min(5), 99, 100);
+        assert_eq!(values, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn enumeration_has_a_limit() {
+        assert_eq!(enumerate_until(|i| i, usize::MAX, 10).len(), 10);
+    }
+
+    #[test]
+    fn frame_types_are_numbered_in_order() {
+        let frames = frame_types();
+        assert!(!frames.is_empty());
+        assert_eq!(frames[0].frame, FrameType::NoBox);
+        for (i, info) in frames.iter().enumerate() {
+            assert_eq!(info.index, i);
+            assert_eq!(info.frame, FrameType::by_index(i));
+        }
+        assert!(frames.iter().all(|f| f.frame != FrameType::FreeBoxType));
+    }
+}
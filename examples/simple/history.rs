@@ -0,0 +1,232 @@
+use crate::crud::{CrudError, Person, PersonRepository};
+
+// 最多记住多少步
+const HISTORY_LIMIT: usize = 100;
+
+// CRUD 的一次操作，记下前后的数据，才能撤销、重做
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Create(Person),
+    Update { before: Person, after: Person },
+    Delete(Person),
+    DeleteMany(Vec<Person>),
+    Import(Vec<Person>),
+}
+
+impl Command {
+    // 菜单里显示的描述，例如 Delete "Turing, Alan"
+    pub fn describe(&self) -> String {
+        match self {
+            Command::Create(person) => format!("Create \"{}\"", person.display_name()),
+            Command::Update { after, .. } => format!("Update \"{}\"", after.display_name()),
+            Command::Delete(person) => format!("Delete \"{}\"", person.display_name()),
+            Command::DeleteMany(people) => format!("Delete {} record(s)", people.len()),
+            Command::Import(people) => format!("Import {} record(s)", people.len()),
+        }
+    }
+
+    fn undo(&self, repository: &mut PersonRepository) -> Result<(), CrudError> {
+        match self {
+            Command::Create(person) => repository.delete(person.id).map(|_| ()),
+            Command::Update { before, .. } => {
+                repository.update(before.id, &before.name, &before.surname)
+            }
+            Command::Delete(person) => repository.restore(person.clone()),
+            Command::DeleteMany(people) => repository.restore_many(people.clone()),
+            Command::Import(people) => {
+                let ids: Vec<u64> = people.iter().map(|p| p.id).collect();
+                repository.delete_many(&ids)
+            }
+        }
+    }
+
+    fn redo(&self, repository: &mut PersonRepository) -> Result<(), CrudError> {
+        match self {
+            Command::Create(person) => repository.restore(person.clone()),
+            Command::Update { after, .. } => {
+                repository.update(after.id, &after.name, &after.surname)
+            }
+            Command::Delete(person) => repository.delete(person.id).map(|_| ()),
+            Command::DeleteMany(people) => {
+                let ids: Vec<u64> = people.iter().map(|p| p.id).collect();
+                repository.delete_many(&ids)
+            }
+            Command::Import(people) => repository.restore_many(people.clone()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct History {
+    undo_stack: Vec<Command>,
+    redo_stack: Vec<Command>,
+}
+
+impl History {
+    // 新的操作，之前撤销的就不能再重做了
+    pub fn record(&mut self, command: Command) {
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn undo_description(&self) -> Option<String> {
+        self.undo_stack.last().map(|c| c.describe())
+    }
+
+    pub fn redo_description(&self) -> Option<String> {
+        self.redo_stack.last().map(|c| c.describe())
+    }
+
+    // 没有可撤销的返回 None；保存失败时，这一步留在原来的栈里
+    pub fn undo(&mut self, repository: &mut PersonRepository) -> Option<Result<(), CrudError>> {
+        let command = self.undo_stack.pop()?;
+        match command.undo(repository) {
+            Ok(()) => {
+                self.redo_stack.push(command);
+                Some(Ok(()))
+            }
+            Err(e) => {
+                self.undo_stack.push(command);
+                Some(Err(e))
+            }
+        }
+    }
+
+    pub fn redo(&mut self, repository: &mut PersonRepository) -> Option<Result<(), CrudError>> {
+        let command = self.redo_stack.pop()?;
+        match command.redo(repository) {
+            Ok(()) => {
+                self.undo_stack.push(command);
+                Some(Ok(()))
+            }
+            Err(e) => {
+                self.redo_stack.push(command);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Store;
+    use std::fs;
+
+    // 每个测试一个数据文件，互不影响
+    fn repository(name: &str) -> PersonRepository {
+        let path = std::env::temp_dir().join(format!(
+            "history-test-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        PersonRepository::new(Store::new(path), vec![])
+    }
+
+    fn create(repository: &mut PersonRepository, history: &mut History, name: &str) -> Person {
+        let id = repository.create(name, "Turing").unwrap();
+        let person = repository.get(id).cloned().unwrap();
+        history.record(Command::Create(person.clone()));
+        person
+    }
+
+    fn names(repository: &PersonRepository) -> Vec<&str> {
+        repository.all().iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn empty_history_has_nothing_to_undo() {
+        let mut repository = repository("empty");
+        let mut history = History::default();
+
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+        assert_eq!(history.undo_description(), None);
+        assert!(history.undo(&mut repository).is_none());
+        assert!(history.redo(&mut repository).is_none());
+    }
+
+    #[test]
+    fn push_then_undo_and_redo() {
+        let mut repository = repository("undo-redo");
+        let mut history = History::default();
+        create(&mut repository, &mut history, "Alan");
+
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+        assert_eq!(
+            history.undo_description().as_deref(),
+            Some("Create \"Turing, Alan\"")
+        );
+
+        history.undo(&mut repository).unwrap().unwrap();
+        assert!(names(&repository).is_empty());
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+        assert_eq!(
+            history.redo_description().as_deref(),
+            Some("Create \"Turing, Alan\"")
+        );
+
+        history.redo(&mut repository).unwrap().unwrap();
+        assert_eq!(names(&repository), vec!["Alan"]);
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn undo_update_restores_before() {
+        let mut repository = repository("update");
+        let mut history = History::default();
+        let before = create(&mut repository, &mut history, "Alan");
+
+        repository.update(before.id, "Alonzo", "Church").unwrap();
+        let after = repository.get(before.id).cloned().unwrap();
+        history.record(Command::Update { before, after });
+
+        history.undo(&mut repository).unwrap().unwrap();
+        assert_eq!(names(&repository), vec!["Alan"]);
+        history.redo(&mut repository).unwrap().unwrap();
+        assert_eq!(names(&repository), vec!["Alonzo"]);
+    }
+
+    #[test]
+    fn new_command_clears_redo() {
+        let mut repository = repository("clear-redo");
+        let mut history = History::default();
+        create(&mut repository, &mut history, "Alan");
+        history.undo(&mut repository).unwrap().unwrap();
+        assert!(history.can_redo());
+
+        create(&mut repository, &mut history, "Ada");
+        assert!(!history.can_redo());
+        assert!(history.redo(&mut repository).is_none());
+        assert_eq!(names(&repository), vec!["Ada"]);
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut history = History::default();
+        let person = Person {
+            id: 1,
+            name: "Alan".to_string(),
+            surname: "Turing".to_string(),
+        };
+        for _ in 0..HISTORY_LIMIT + 10 {
+            history.record(Command::Create(person.clone()));
+        }
+        assert_eq!(history.undo_stack.len(), HISTORY_LIMIT);
+    }
+}
@@ -0,0 +1,585 @@
+use std::{cell::Cell, rc::Rc};
+
+use fltk::{
+    app,
+    button::{Button, CheckButton},
+    dialog::{color_chooser, ColorMode},
+    enums::{Align, Color, Event, Font, FrameType},
+    group::{Group, Tabs},
+    menu::Choice,
+    misc::Spinner,
+    output::MultilineOutput,
+    prelude::*,
+    widget::Widget,
+};
+
+use crate::frame_browser::FrameInfo;
+use crate::{WIDGET_HEIGHT, WIDGET_PADDING};
+
+// gallery 右边的属性面板：选中一个 widget，直接改它的颜色、字体、对齐、边框、状态，
+// 再生成一段对应的 builder 代码
+
+const LABEL_WIDTH: i32 = 80;
+const ROW_HEIGHT: i32 = WIDGET_HEIGHT + 5;
+const SNIPPET_HEIGHT: i32 = 120;
+const FONT_SIZE_MIN: f64 = 6.0;
+const FONT_SIZE_MAX: f64 = 48.0;
+
+const FONT_NAMES: [&str; 16] = [
+    "Helvetica",
+    "HelveticaBold",
+    "HelveticaItalic",
+    "HelveticaBoldItalic",
+    "Courier",
+    "CourierBold",
+    "CourierItalic",
+    "CourierBoldItalic",
+    "Times",
+    "TimesBold",
+    "TimesItalic",
+    "TimesBoldItalic",
+    "Symbol",
+    "Screen",
+    "ScreenBold",
+    "Zapfdingbats",
+];
+
+// 对齐拆成横、竖两个下拉框加一个 Inside
+const HORIZONTAL_ALIGNS: [(&str, i32); 3] = [("Center", 0), ("Left", 4), ("Right", 8)];
+const VERTICAL_ALIGNS: [(&str, i32); 3] = [("Center", 0), ("Top", 1), ("Bottom", 2)];
+const ALIGN_NAMES: [(&str, i32); 7] = [
+    ("Top", 1),
+    ("Bottom", 2),
+    ("Left", 4),
+    ("Right", 8),
+    ("Inside", 16),
+    ("Clip", 64),
+    ("Wrap", 128),
+];
+const HORIZONTAL_MASK: i32 = 4 | 8;
+const VERTICAL_MASK: i32 = 1 | 2;
+const INSIDE: i32 = 16;
+
+// gallery 里可以被检查的 widget
+#[derive(Clone)]
+pub struct GalleryItem {
+    // 生成代码时的变量名
+    pub name: &'static str,
+    // 生成代码时的类型名
+    pub type_name: &'static str,
+    pub widget: Widget,
+}
+
+impl GalleryItem {
+    pub fn new<W: WidgetExt>(name: &'static str, type_name: &'static str, widget: &W) -> Self {
+        Self {
+            name,
+            type_name,
+            widget: widget.as_base_widget(),
+        }
+    }
+}
+
+// 面板上能改的属性；不依赖 widget，生成代码只看这个
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetProps {
+    pub size: (i32, i32),
+    pub label: String,
+    pub color: (u8, u8, u8),
+    pub font: usize,
+    pub font_size: i32,
+    pub align: i32,
+    pub frame: String,
+    pub active: bool,
+    pub visible: bool,
+}
+
+impl WidgetProps {
+    fn read<W: WidgetExt>(widget: &W) -> Self {
+        Self {
+            size: (widget.w(), widget.h()),
+            label: widget.label(),
+            color: widget.color().to_rgb(),
+            font: widget.label_font().bits() as usize,
+            font_size: widget.label_size(),
+            align: widget.align().bits(),
+            frame: format!("{:?}", widget.frame()),
+            active: widget.active(),
+            visible: widget.visible(),
+        }
+    }
+}
+
+// Align 的 bits 写成 Align::Left | Align::Inside 这样
+pub fn align_expr(bits: i32) -> String {
+    let names: Vec<String> = ALIGN_NAMES
+        .iter()
+        .filter(|(_, bit)| bits & bit != 0)
+        .map(|(name, _)| format!("Align::{}", name))
+        .collect();
+    if names.is_empty() {
+        "Align::Center".to_string()
+    } else {
+        names.join(" | ")
+    }
+}
+
+// 生成 fltk-rs 的 builder 写法
+pub fn builder_snippet(name: &str, type_name: &str, props: &WidgetProps) -> String {
+    let (w, h) = props.size;
+    let (r, g, b) = props.color;
+    let font = FONT_NAMES.get(props.font).copied().unwrap_or("Helvetica");
+
+    let mut lines = vec![format!("let mut {} = {}::default()", name, type_name)];
+    lines.push(format!("    .with_size({}, {})", w, h));
+    if !props.label.is_empty() {
+        lines.push(format!("    .with_label({:?})", props.label));
+    }
+    lines.push(format!("    .with_align({});", align_expr(props.align)));
+    lines.push(format!(
+        "{}.set_color(Color::from_rgb({}, {}, {}));",
+        name, r, g, b
+    ));
+    lines.push(format!("{}.set_label_font(Font::{});", name, font));
+    lines.push(format!("{}.set_label_size({});", name, props.font_size));
+    lines.push(format!("{}.set_frame(FrameType::{});", name, props.frame));
+    if !props.active {
+        lines.push(format!("{}.deactivate();", name));
+    }
+    if !props.visible {
+        lines.push(format!("{}.hide();", name));
+    }
+    lines.join("\n")
+}
+
+fn choice_index(options: &[(&str, i32)], bits: i32) -> i32 {
+    options
+        .iter()
+        .position(|(_, bit)| *bit == bits)
+        .unwrap_or(0) as i32
+}
+
+#[derive(Clone)]
+struct Controls {
+    widget_choice: Choice,
+    color_button: Button,
+    font_choice: Choice,
+    size_spinner: Spinner,
+    horizontal_choice: Choice,
+    vertical_choice: Choice,
+    inside_check: CheckButton,
+    frame_choice: Choice,
+    active_check: CheckButton,
+    visible_check: CheckButton,
+    snippet_output: MultilineOutput,
+}
+
+#[derive(Clone)]
+pub struct Inspector {
+    group: Group,
+    controls: Controls,
+    items: Rc<Vec<GalleryItem>>,
+    // Frame 下拉框里的顺序
+    frames: Rc<Vec<FrameInfo>>,
+    selected: Rc<Cell<usize>>,
+}
+
+impl Inspector {
+    pub fn new(
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        items: Vec<GalleryItem>,
+        frames: Rc<Vec<FrameInfo>>,
+    ) -> Self {
+        let mut group = Group::new(x, y, w, h, None);
+        group.set_frame(FrameType::EngravedBox);
+
+        let left = x + WIDGET_PADDING + LABEL_WIDTH;
+        let width = w - WIDGET_PADDING * 2 - LABEL_WIDTH;
+        let row = |i: i32| y + WIDGET_PADDING + ROW_HEIGHT * i;
+
+        let mut widget_choice = Choice::new(left, row(0), width, WIDGET_HEIGHT, "Widget:");
+        for item in &items {
+            widget_choice.add_choice(item.name);
+        }
+
+        let mut color_button = Button::new(left, row(1), width, WIDGET_HEIGHT, "Color:");
+        color_button.set_align(Align::Left);
+
+        let mut font_choice = Choice::new(left, row(2), width, WIDGET_HEIGHT, "Font:");
+        for name in FONT_NAMES {
+            font_choice.add_choice(name);
+        }
+
+        let mut size_spinner = Spinner::new(left, row(3), width / 3, WIDGET_HEIGHT, "Size:");
+        size_spinner.set_range(FONT_SIZE_MIN, FONT_SIZE_MAX);
+        size_spinner.set_step(1.0);
+
+        let third = width / 3;
+        let mut horizontal_choice = Choice::new(left, row(4), third, WIDGET_HEIGHT, "Align:");
+        for (name, _) in HORIZONTAL_ALIGNS {
+            horizontal_choice.add_choice(name);
+        }
+        let mut vertical_choice = Choice::new(left + third, row(4), third, WIDGET_HEIGHT, None);
+        for (name, _) in VERTICAL_ALIGNS {
+            vertical_choice.add_choice(name);
+        }
+        let inside_check = CheckButton::new(
+            left + third * 2,
+            row(4),
+            width - third * 2,
+            WIDGET_HEIGHT,
+            "Inside",
+        );
+
+        let mut frame_choice = Choice::new(left, row(5), width, WIDGET_HEIGHT, "Frame:");
+        for info in frames.iter() {
+            frame_choice.add_choice(&info.name);
+        }
+
+        let active_check = CheckButton::new(left, row(6), width / 2, WIDGET_HEIGHT, "Active");
+        let visible_check = CheckButton::new(
+            left + width / 2,
+            row(6),
+            width - width / 2,
+            WIDGET_HEIGHT,
+            "Visible",
+        );
+
+        let mut snippet_output = MultilineOutput::new(
+            x + WIDGET_PADDING,
+            row(7),
+            w - WIDGET_PADDING * 2,
+            SNIPPET_HEIGHT,
+            None,
+        );
+        snippet_output.set_text_font(Font::Courier);
+        snippet_output.set_text_size(app::font_size() - 2);
+
+        let mut copy_button = Button::new(
+            x + w - WIDGET_PADDING - width / 2,
+            row(7) + SNIPPET_HEIGHT + WIDGET_PADDING,
+            width / 2,
+            WIDGET_HEIGHT,
+            "Copy snippet",
+        );
+
+        group.end();
+
+        let mut inspector = Self {
+            group,
+            controls: Controls {
+                widget_choice,
+                color_button,
+                font_choice,
+                size_spinner,
+                horizontal_choice,
+                vertical_choice,
+                inside_check,
+                frame_choice,
+                active_check,
+                visible_check,
+                snippet_output,
+            },
+            items: Rc::new(items),
+            frames,
+            selected: Rc::new(Cell::new(0)),
+        };
+
+        let mut controls = inspector.controls.clone();
+        controls.widget_choice.set_callback({
+            let mut inspector = inspector.clone();
+            move |c| {
+                if let Ok(index) = usize::try_from(c.value()) {
+                    inspector.select(index);
+                }
+            }
+        });
+        controls.color_button.set_callback({
+            let mut inspector = inspector.clone();
+            move |_| {
+                if let Some((r, g, b)) = color_chooser("Widget color", ColorMode::Rgb) {
+                    inspector.edit(|w| w.set_color(Color::from_rgb(r, g, b)));
+                }
+            }
+        });
+        controls.font_choice.set_callback({
+            let mut inspector = inspector.clone();
+            move |c| {
+                let font = Font::by_index(c.value().max(0) as usize);
+                inspector.edit(|w| w.set_label_font(font));
+            }
+        });
+        controls.size_spinner.set_callback({
+            let mut inspector = inspector.clone();
+            move |s| {
+                let size = s.value() as i32;
+                inspector.edit(|w| w.set_label_size(size));
+            }
+        });
+
+        controls.horizontal_choice.set_callback({
+            let mut inspector = inspector.clone();
+            move |_| inspector.edit_align()
+        });
+        controls.vertical_choice.set_callback({
+            let mut inspector = inspector.clone();
+            move |_| inspector.edit_align()
+        });
+        controls.inside_check.set_callback({
+            let mut inspector = inspector.clone();
+            move |_| inspector.edit_align()
+        });
+        controls.frame_choice.set_callback({
+            let mut inspector = inspector.clone();
+            move |c| {
+                let Some(info) = usize::try_from(c.value())
+                    .ok()
+                    .and_then(|i| inspector.frames.get(i))
+                else {
+                    return;
+                };
+                let frame = info.frame;
+                inspector.edit(|w| w.set_frame(frame));
+            }
+        });
+        controls.active_check.set_callback({
+            let mut inspector = inspector.clone();
+            move |c| {
+                let active = c.is_checked();
+                inspector.edit(|w| if active { w.activate() } else { w.deactivate() });
+            }
+        });
+        controls.visible_check.set_callback({
+            let mut inspector = inspector.clone();
+            move |c| {
+                let visible = c.is_checked();
+                inspector.edit(|w| if visible { w.show() } else { w.hide() });
+            }
+        });
+        copy_button.set_callback({
+            let inspector = inspector.clone();
+            move |_| app::copy(&inspector.controls.snippet_output.value())
+        });
+
+        inspector.select(0);
+        inspector
+    }
+
+    // 在 gallery 里点一下 widget 也能选中它；事件照常交给 widget 处理
+    pub fn follow_clicks(&self, tabs: &mut Tabs) {
+        let mut inspector = self.clone();
+        tabs.handle(move |_, ev| {
+            if ev == Event::Push {
+                let (x, y) = app::event_coords();
+                let hit = inspector.items.iter().position(|item| {
+                    let w = &item.widget;
+                    w.visible_r()
+                        && x >= w.x()
+                        && x < w.x() + w.w()
+                        && y >= w.y()
+                        && y < w.y() + w.h()
+                });
+                if let Some(index) = hit {
+                    inspector.select(index);
+                }
+            }
+            false
+        });
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+        self.selected.set(index);
+        self.controls.widget_choice.set_value(index as i32);
+        self.load();
+    }
+
+    // 把选中 widget 现在的属性填到控件里
+    fn load(&mut self) {
+        let Some(item) = self.items.get(self.selected.get()) else {
+            return;
+        };
+        let widget = &item.widget;
+        let props = WidgetProps::read(widget);
+        let controls = &mut self.controls;
+
+        controls.color_button.set_color(widget.color());
+        controls.color_button.redraw();
+        controls.font_choice.set_value(props.font as i32);
+        controls.size_spinner.set_value(props.font_size as f64);
+        controls.horizontal_choice.set_value(choice_index(
+            &HORIZONTAL_ALIGNS,
+            props.align & HORIZONTAL_MASK,
+        ));
+        controls
+            .vertical_choice
+            .set_value(choice_index(&VERTICAL_ALIGNS, props.align & VERTICAL_MASK));
+        controls.inside_check.set_checked(props.align & INSIDE != 0);
+        let frame = self
+            .frames
+            .iter()
+            .position(|info| info.frame == widget.frame())
+            .unwrap_or(0);
+        controls.frame_choice.set_value(frame as i32);
+        controls.active_check.set_checked(props.active);
+        controls.visible_check.set_checked(props.visible);
+
+        controls
+            .snippet_output
+            .set_value(&builder_snippet(item.name, item.type_name, &props));
+    }
+
+    // 三个对齐控件改的都是同一个 Align，其他 bit 保留
+    fn edit_align(&mut self) {
+        let controls = &self.controls;
+        let horizontal = HORIZONTAL_ALIGNS[controls.horizontal_choice.value().max(0) as usize].1;
+        let vertical = VERTICAL_ALIGNS[controls.vertical_choice.value().max(0) as usize].1;
+        let inside = if controls.inside_check.is_checked() {
+            INSIDE
+        } else {
+            0
+        };
+        self.edit(|w| {
+            let kept = w.align().bits() & !(HORIZONTAL_MASK | VERTICAL_MASK | INSIDE);
+            w.set_align(Align::from_bits_retain(
+                kept | horizontal | vertical | inside,
+            ));
+        });
+    }
+
+    // 改选中的 widget，然后刷新面板
+    fn edit(&mut self, change: impl FnOnce(&mut Widget)) {
+        let Some(item) = self.items.get(self.selected.get()) else {
+            return;
+        };
+        let mut widget = item.widget.clone();
+        change(&mut widget);
+
+        // 边框、隐藏会改到 widget 外面的区域，整个父控件一起重画
+        match widget.parent() {
+            Some(mut parent) => parent.redraw(),
+            None => widget.redraw(),
+        }
+        self.load();
+        self.group.redraw();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props() -> WidgetProps {
+        WidgetProps {
+            size: (70, 25),
+            label: "Ok".to_string(),
+            color: (255, 0, 128),
+            font: 1,
+            font_size: 14,
+            align: 0,
+            frame: "UpBox".to_string(),
+            active: true,
+            visible: true,
+        }
+    }
+
+    #[test]
+    fn align_without_bits_is_center() {
+        assert_eq!(align_expr(0), "Align::Center");
+        // 32 是 TextOverImage，面板上改不了，也当成 Center
+        assert_eq!(align_expr(32), "Align::Center");
+    }
+
+    #[test]
+    fn align_bits_are_joined_in_order() {
+        assert_eq!(align_expr(4 | 16), "Align::Left | Align::Inside");
+        assert_eq!(
+            align_expr(16 | 8 | 1),
+            "Align::Top | Align::Right | Align::Inside"
+        );
+        assert_eq!(align_expr(2 | 128), "Align::Bottom | Align::Wrap");
+    }
+
+    #[test]
+    fn snippet_for_an_active_visible_widget() {
+        assert_eq!(
+            builder_snippet("ok", "Button", &props()),
+            [
+                "let mut ok = Button::default()",
+                "    .with_size(70, 25)",
+                "    .with_label(\"Ok\")",
+                "    .with_align(Align::Center);",
+                "ok.set_color(Color::from_rgb(255, 0, 128));",
+                "ok.set_label_font(Font::HelveticaBold);",
+                "ok.set_label_size(14);",
+                "ok.set_frame(FrameType::UpBox);",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn empty_label_is_left_out() {
+        let props = WidgetProps {
+            label: String::new(),
+            ..props()
+        };
+        let snippet = builder_snippet("out", "Output", &props);
+        assert!(!snippet.contains("with_label"));
+        assert!(snippet.contains("    .with_size(70, 25)\n    .with_align(Align::Center);"));
+    }
+
+    #[test]
+    fn label_is_quoted() {
+        let props = WidgetProps {
+            label: "Say \"hi\"".to_string(),
+            ..props()
+        };
+        assert!(builder_snippet("b", "Button", &props).contains(".with_label(\"Say \\\"hi\\\"\")"));
+    }
+
+    #[test]
+    fn unknown_font_falls_back_to_helvetica() {
+        let props = WidgetProps {
+            font: FONT_NAMES.len(),
+            ..props()
+        };
+        assert!(
+            builder_snippet("b", "Button", &props).contains("b.set_label_font(Font::Helvetica);")
+        );
+        let props = WidgetProps {
+            font: FONT_NAMES.len() - 1,
+            ..props
+        };
+        assert!(builder_snippet("b", "Button", &props)
+            .contains("b.set_label_font(Font::Zapfdingbats);"));
+    }
+
+    #[test]
+    fn inactive_and_hidden_widgets() {
+        let snippet = builder_snippet("b", "Button", &props());
+        assert!(!snippet.contains("deactivate"));
+        assert!(!snippet.contains("hide"));
+
+        let props = WidgetProps {
+            active: false,
+            visible: false,
+            ..props()
+        };
+        let snippet = builder_snippet("b", "Button", &props);
+        assert!(snippet.ends_with("b.deactivate();\nb.hide();"));
+
+        let props = WidgetProps {
+            visible: true,
+            ..props
+        };
+        let snippet = builder_snippet("b", "Button", &props);
+        assert!(snippet.ends_with("b.deactivate();"));
+        assert!(!snippet.contains("hide"));
+    }
+}
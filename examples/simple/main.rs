@@ -0,0 +1,2215 @@
+use std::{
+    cell::{Cell, RefCell},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use fltk::{
+    app::{self, channel},
+    browser::{Browser, HoldBrowser},
+    button::*,
+    dialog::{
+        alert_default, choice2_default, NativeFileChooser, NativeFileChooserOptions,
+        NativeFileChooserType,
+    },
+    enums::{self, Align, CallbackTrigger, Color, Event, FrameType, Key, Shortcut},
+    frame::Frame,
+    group::{Flex, Group, Pack, PackType, Tabs, Wizard},
+    input::Input,
+    menu::{Choice, MenuButton, MenuFlag},
+    misc::{Progress, Spinner},
+    output::Output,
+    prelude::*,
+    valuator::HorSlider,
+    widget::{self, Widget},
+    widget_extends,
+    window::Window,
+};
+
+use chrono::{offset::Local, NaiveDate};
+use hello_fltk::{
+    calculator::Calculator,
+    pricing::DEFAULT_BASE_URL,
+    settings::{Settings, SETTINGS_FILE_PATH},
+    theme::{self, builtin_themes, load_themes, SchemeName, THEMES_FILE_PATH},
+    widgets::StyledButton,
+};
+
+mod bookings_panel;
+mod calendar;
+mod counter;
+mod crud;
+mod dates;
+mod event_log;
+mod filter;
+mod flights;
+mod frame_browser;
+mod history;
+mod inspector;
+mod person_table;
+mod pricing_panel;
+mod stopwatch;
+mod store;
+mod temperature;
+mod transfer;
+mod validate;
+
+use bookings_panel::BookingsPanel;
+use calendar::pick_date;
+use counter::ClickCounter;
+use crud::{CrudError, Person, PersonRepository};
+use dates::{parse_date, DateError, DateFormat};
+use event_log::EventLog;
+use filter::{filter_people, MatchMode, Query, SortColumn, SortDirection};
+use flights::{BookingStore, Inventory, Leg, Route, BOOKINGS_FILE_PATH, INVENTORY_FILE_PATH};
+use frame_browser::{frame_types, show_frame_browser};
+use history::{Command, History};
+use inspector::{GalleryItem, Inspector};
+use person_table::{Column, PersonTable, TableEvent};
+use pricing_panel::PricingPanel;
+use stopwatch::{Stopwatch, Ticker};
+use store::{Store, STORE_FILE_PATH};
+use temperature::{convert, format_temperature, parse_temperature, Unit};
+use transfer::{export, guess_mapping, plan_import, read_table, Format, Mapping, Table};
+use validate::{FieldRules, NAME_RULES, SURNAME_RULES};
+
+const WIDGET_HEIGHT: i32 = 25;
+const WIDGET_PADDING: i32 = 10;
+const WIDGET_WIDTH: i32 = 70;
+
+const BOOKING_WIDGET_WIDTH: i32 = 200;
+
+// 左边的工具列表；右边放工具的区域从 CONTENT_X 开始
+const SIDEBAR_WIDTH: i32 = 150;
+const CONTENT_X: i32 = SIDEBAR_WIDTH;
+const CONTENT_WIDTH: i32 = 540;
+const WINDOW_HEIGHT: i32 = 500;
+
+const WIDGET_LABEL_WIDTH: i32 = 100;
+const PROGRESS_WIDGET_WIDTH: i32 = 200;
+// gallery 右边的属性面板
+const INSPECTOR_WIDTH: i32 = 290;
+// 温度单位符号那一小格
+const TEMPERATURE_SYMBOL_WIDTH: i32 = 25;
+const TEMPERATURE_DEFAULT_PRECISION: usize = 1;
+const TEMPERATURE_MAX_PRECISION: usize = 6;
+const DURATION_DEFAULT: f64 = 15.0;
+const DURATION_MAXIMUM: f64 = 30.0;
+// 多久刷新一次进度条；经过的时间按 Instant 算，和这个间隔无关
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+// 日历里最多能选到多少天以后
+const BOOKING_HORIZON_DAYS: i64 = 365;
+
+// 导入预览最多显示几行
+const IMPORT_PREVIEW_ROWS: usize = 20;
+
+#[derive(Clone, Copy)]
+enum Message {
+    SchemeChanged,
+    ThemeChanged,
+    ToolSelected,
+
+    // 哪个单位的输入框改了
+    TemperatureChanged(Unit),
+    TemperaturePrecision,
+
+    BookingUpdate,
+    BookingBook,
+    BookingShowList,
+    BookingSelect,
+    BookingCancel,
+    BookingPickStart,
+    BookingPickReturn,
+    BookingFormatChanged,
+    BookingNormalize,
+
+    TimerReset,
+    TimerChangeDuration,
+    TimerTick,
+    TimerPauseRunning,
+
+    CrudClear,
+    CrudCreate,
+    CrudUpdate,
+    CrudDelete,
+    CrudSelect,
+    CrudCellEdited,
+    CrudFormChanged,
+    CrudFilter,
+    CrudUndo,
+    CrudRedo,
+    CrudToggleConfirm,
+    CrudImport,
+    CrudExport,
+
+    // 编号改了，重新数一下
+    PricingEdited,
+    PricingSend,
+    // 后台线程的请求回来了
+    PricingReceived,
+}
+
+// 启动器左边列出的工具，列表里的顺序就是 ALL 的顺序
+// Timer 是 7GUIs 的计时器。count_down 的倒计时不放进来：它有自己的 ChannelMessage 循环，
+// mini 模式要把自己的窗口变成无边框、置顶的小窗口，还有单独的帮助窗口，都不能放在一个页面里；
+// 要放进来得先把它改成 Group widget，搬到 src/ 里
+#[derive(Clone, Copy, PartialEq)]
+enum Tool {
+    Counter,
+    Converter,
+    Booker,
+    Timer,
+    Crud,
+    Gallery,
+    Calculator,
+    Pricing,
+}
+
+impl Tool {
+    const ALL: [Tool; 8] = [
+        Tool::Counter,
+        Tool::Converter,
+        Tool::Booker,
+        Tool::Timer,
+        Tool::Crud,
+        Tool::Gallery,
+        Tool::Calculator,
+        Tool::Pricing,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Tool::Counter => "Counter",
+            Tool::Converter => "Converter",
+            Tool::Booker => "Flight booker",
+            Tool::Timer => "Timer",
+            Tool::Crud => "CRUD",
+            Tool::Gallery => "Gallery",
+            Tool::Calculator => "Calculator",
+            Tool::Pricing => "Pricing",
+        }
+    }
+
+    // 设置里存的是名字，改了顺序也不会打开错
+    fn from_label(label: &str) -> Option<Tool> {
+        Tool::ALL.iter().copied().find(|t| t.label() == label)
+    }
+
+    fn index(self) -> usize {
+        Tool::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TimerStatus {
+    Running,
+    Pause,
+}
+
+fn main() {
+    let mut app = app::App::default();
+
+    // 主题要在创建 widget 之前设好，新建的 widget 才会用主题的字号
+    let mut settings = Settings::load(SETTINGS_FILE_PATH);
+    let (themes, theme_error) = match load_themes(THEMES_FILE_PATH) {
+        Ok(themes) => (themes, None),
+        Err(e) => (builtin_themes(), Some(e)),
+    };
+    let theme_index = settings.theme_index(&themes);
+    let scheme = settings.scheme_for(&themes[theme_index]);
+    theme::apply(&themes[theme_index], scheme);
+
+    let mut timer_status = TimerStatus::Running;
+
+    // 消息
+    let (sender, receiver) = channel::<Message>();
+
+    // 暂停、到时间的时候通知线程停下来，不再发 TimerTick
+    let ticker = Ticker::spawn(TICK_INTERVAL, move || sender.send(Message::TimerTick));
+    let mut stopwatch = Stopwatch::default();
+    stopwatch.start(Instant::now());
+    ticker.resume();
+
+    let mut wind = Window::new(
+        100,
+        100,
+        SIDEBAR_WIDTH + CONTENT_WIDTH,
+        WINDOW_HEIGHT,
+        "Hello from rust",
+    );
+
+    // 左边：工具列表，下面是 scheme 和主题，所有工具共用
+    let mut theme_choice = Choice::default()
+        .with_size(SIDEBAR_WIDTH - WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .with_pos(
+            WIDGET_PADDING,
+            WINDOW_HEIGHT - WIDGET_HEIGHT - WIDGET_PADDING,
+        )
+        .with_align(Align::TopLeft)
+        .with_label("Theme:");
+    for theme in &themes {
+        theme_choice.add_choice(&theme.name.replace('/', "\\/").replace('&', "&&"));
+    }
+    theme_choice.set_value(theme_index as i32);
+    theme_choice.emit(sender, Message::ThemeChanged);
+
+    let mut choice_scheme = Choice::default()
+        .with_size(SIDEBAR_WIDTH - WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .above_of(&theme_choice, WIDGET_HEIGHT)
+        .with_align(Align::TopLeft)
+        .with_label("Scheme:");
+
+    SchemeName::ALL.iter().for_each(|s| {
+        choice_scheme.add_choice(s.label());
+    });
+
+    choice_scheme.set_value(scheme_index(scheme));
+    choice_scheme.emit(sender, Message::SchemeChanged);
+
+    let mut tool_browser = HoldBrowser::new(
+        0,
+        0,
+        SIDEBAR_WIDTH,
+        choice_scheme.y() - WIDGET_HEIGHT - WIDGET_PADDING,
+        None,
+    );
+    for tool in Tool::ALL {
+        tool_browser.add(tool.label());
+    }
+    tool_browser.emit(sender, Message::ToolSelected);
+
+    // 右边：一次只显示一个工具，每个工具一个 Group，顺序和 Tool::ALL 一样
+    let mut tool_wizard = Wizard::new(CONTENT_X, 0, CONTENT_WIDTH, WINDOW_HEIGHT, None);
+    let mut tool_groups: Vec<Group> = vec![];
+
+    // 1. 计数
+    let counter_group = tool_group();
+
+    // frame 就是 label
+    let mut frame = Frame::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(CONTENT_X + WIDGET_PADDING, WIDGET_PADDING)
+        .with_label("Change Me");
+
+    let mut but = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&frame, WIDGET_PADDING)
+        .with_label("Click Me");
+
+    // output 就是 inputbox 的 subclass
+    // 指定大小，指定絕對位置，第一排
+    let mut output = Output::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&frame, WIDGET_PADDING * 2);
+    output.set_value("0");
+
+    // 橫向排列
+    let mut button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&output, WIDGET_PADDING)
+        .with_label("Count");
+
+    counter_group.end();
+    tool_groups.push(counter_group);
+
+    // 2. 温度转换器：改任意一格，其他几格跟着换算
+    let converter_group = tool_group();
+
+    let mut temperature_inputs: Vec<Input> = vec![];
+    let mut temperature_x = CONTENT_X + WIDGET_PADDING;
+    for unit in Unit::ALL {
+        let mut input = Input::default()
+            .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+            .with_pos(temperature_x, WIDGET_PADDING);
+        // 發送消息
+        input.set_trigger(CallbackTrigger::Changed);
+        input.emit(sender, Message::TemperatureChanged(unit));
+
+        let symbol_frame = Frame::default()
+            .with_size(TEMPERATURE_SYMBOL_WIDTH, WIDGET_HEIGHT)
+            .right_of(&input, 0)
+            .with_label(unit.symbol());
+        temperature_x = symbol_frame.x() + symbol_frame.w() + WIDGET_PADDING;
+        temperature_inputs.push(input);
+    }
+
+    let mut precision_spinner = Spinner::default()
+        .with_size(TEMPERATURE_SYMBOL_WIDTH * 2, WIDGET_HEIGHT)
+        .with_pos(temperature_x, WIDGET_PADDING);
+    precision_spinner.set_range(0.0, TEMPERATURE_MAX_PRECISION as f64);
+    precision_spinner.set_step(1.0);
+    precision_spinner.set_value(TEMPERATURE_DEFAULT_PRECISION as f64);
+    precision_spinner.set_tooltip("Decimal places");
+    precision_spinner.emit(sender, Message::TemperaturePrecision);
+
+    // 用户最后输入的单位和数值；换精度时从它重新算，不拿四舍五入过的结果再算
+    let mut temperature_source: Option<(Unit, f64)> = None;
+
+    converter_group.end();
+    tool_groups.push(converter_group);
+
+    // 3. booking flight
+    let booker_group = tool_group();
+
+    let frame3 = Frame::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(CONTENT_X + WIDGET_PADDING, WIDGET_PADDING)
+        .with_label("Booking Flight");
+
+    // 日期的显示格式；输入时各种写法都接受
+    let mut date_format = DateFormat::Iso;
+    let mut date_format_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&frame3, WIDGET_LABEL_WIDTH)
+        .with_label("Date format:");
+    for format in DateFormat::ALL {
+        // 菜单的 label 里 / 是子菜单
+        date_format_choice.add_choice(&format.label().replace('/', "\\/"));
+    }
+    date_format_choice.set_value(0);
+    date_format_choice.emit(sender, Message::BookingFormatChanged);
+
+    let mut choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&frame3, WIDGET_PADDING);
+    choice.add_choice("one-way flight");
+    choice.add_choice("return flight");
+    let one_way_flight_index = 0;
+    choice.set_value(one_way_flight_index);
+    choice.emit(sender, Message::BookingUpdate);
+
+    // 航线、去程和回程的航班，从库存文件读；选项后面显示剩几个座位
+    let mut route_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&choice, WIDGET_LABEL_WIDTH)
+        .with_label("Route:");
+    route_choice.emit(sender, Message::BookingUpdate);
+
+    let current_date = Local::now().naive_local().date();
+
+    // 输入框右边的按钮弹出日历
+    let mut start_input = Input::default()
+        .with_size(BOOKING_WIDGET_WIDTH - WIDGET_HEIGHT, WIDGET_HEIGHT)
+        .below_of(&choice, WIDGET_PADDING);
+    start_input.set_trigger(CallbackTrigger::Changed);
+    start_input.emit(sender, Message::BookingUpdate);
+    start_input.set_value(&date_format.format(current_date));
+
+    let mut start_pick_button = Button::default()
+        .with_size(WIDGET_HEIGHT, WIDGET_HEIGHT)
+        .right_of(&start_input, 0)
+        .with_label("@menu");
+    start_pick_button.set_tooltip("Pick a date");
+    start_pick_button.emit(sender, Message::BookingPickStart);
+
+    let mut outbound_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&start_pick_button, WIDGET_LABEL_WIDTH)
+        .with_label("Outbound:");
+    outbound_choice.emit(sender, Message::BookingUpdate);
+
+    let mut return_input = Input::default()
+        .with_size(BOOKING_WIDGET_WIDTH - WIDGET_HEIGHT, WIDGET_HEIGHT)
+        .below_of(&start_input, WIDGET_PADDING);
+    return_input.deactivate();
+    return_input.set_trigger(CallbackTrigger::Changed);
+    return_input.emit(sender, Message::BookingUpdate);
+    return_input.set_value(&date_format.format(current_date));
+
+    // 编辑完 (离开输入框或者按回车) 再统一成显示格式，输入的时候不打扰
+    for input in [&mut start_input, &mut return_input] {
+        input.handle(move |_, ev| {
+            let enter =
+                ev == Event::KeyDown && matches!(app::event_key(), Key::Enter | Key::KPEnter);
+            if ev == Event::Unfocus || enter {
+                sender.send(Message::BookingNormalize);
+            }
+            false
+        });
+    }
+
+    let mut return_pick_button = Button::default()
+        .with_size(WIDGET_HEIGHT, WIDGET_HEIGHT)
+        .right_of(&return_input, 0)
+        .with_label("@menu");
+    return_pick_button.set_tooltip("Pick a date");
+    return_pick_button.emit(sender, Message::BookingPickReturn);
+    return_pick_button.deactivate();
+
+    let mut inbound_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&return_pick_button, WIDGET_LABEL_WIDTH)
+        .with_label("Return:");
+    inbound_choice.deactivate();
+    inbound_choice.emit(sender, Message::BookingUpdate);
+
+    let mut book_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&return_input, WIDGET_PADDING)
+        .with_label("Book");
+    book_button.emit(sender, Message::BookingBook);
+
+    let mut bookings_button = Button::default()
+        .with_size(WIDGET_WIDTH + WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .right_of(&book_button, WIDGET_PADDING)
+        .with_label("Bookings...");
+    bookings_button.emit(sender, Message::BookingShowList);
+
+    let inventory = load_inventory();
+    let routes = inventory.routes();
+    for route in &routes {
+        route_choice.add_choice(&route.to_string());
+    }
+    route_choice.set_value(0);
+
+    let mut bookings = load_bookings();
+    // Choice 的第 n 项对应的航班代码
+    let mut outbound_codes: Vec<String> = vec![];
+    let mut inbound_codes: Vec<String> = vec![];
+    sender.send(Message::BookingUpdate);
+
+    booker_group.end();
+    tool_groups.push(booker_group);
+
+    // 4. Timer
+    let timer_group = tool_group();
+
+    let frame4 = Frame::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(CONTENT_X + WIDGET_PADDING, WIDGET_PADDING)
+        .with_label("Timer");
+
+    let mut elapsed_progress = Progress::default()
+        .with_size(PROGRESS_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            CONTENT_X + WIDGET_PADDING + WIDGET_LABEL_WIDTH,
+            frame4.y() + frame4.h() + WIDGET_PADDING,
+        )
+        .with_align(Align::Left)
+        .with_label("Elapsed Time:");
+    elapsed_progress.set_selection_color(themes[theme_index].accent.color());
+    elapsed_progress.set_maximum(DURATION_DEFAULT);
+
+    let mut elapsed_frame = Frame::default()
+        .with_size(PROGRESS_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&elapsed_progress, WIDGET_PADDING)
+        .with_label("0.0s")
+        .with_align(Align::Inside | Align::Left);
+
+    let mut duration_slider = HorSlider::default()
+        .with_size(PROGRESS_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&elapsed_progress, WIDGET_HEIGHT + WIDGET_PADDING * 2)
+        .with_align(Align::Left)
+        .with_label("Duration:");
+    duration_slider.set_value(DURATION_DEFAULT);
+    duration_slider.set_maximum(DURATION_MAXIMUM);
+    duration_slider.emit(sender, Message::TimerChangeDuration);
+
+    let mut reset_button = Button::default()
+        .with_size(WIDGET_LABEL_WIDTH + PROGRESS_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            CONTENT_X + WIDGET_PADDING,
+            duration_slider.y() + duration_slider.h() + WIDGET_PADDING,
+        )
+        .with_label("Reset");
+    reset_button.emit(sender, Message::TimerReset);
+
+    let mut pause_running_button = Button::default()
+        .with_size(WIDGET_LABEL_WIDTH + PROGRESS_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&reset_button, 0)
+        .with_label("Pause");
+    pause_running_button.emit(sender, Message::TimerPauseRunning);
+
+    timer_group.end();
+    tool_groups.push(timer_group);
+
+    // 5. CRUD
+    let crud_group = tool_group();
+
+    let frame5 = Frame::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(CONTENT_X + WIDGET_PADDING, WIDGET_PADDING)
+        .with_label("CRUD");
+
+    // 撤销、重做；菜单项显示将要撤销的是什么
+    let mut edit_menu = MenuButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&frame5, WIDGET_PADDING)
+        .with_label("Edit");
+    edit_menu.add_emit(
+        "Undo",
+        Shortcut::Ctrl | 'z',
+        MenuFlag::Normal,
+        sender,
+        Message::CrudUndo,
+    );
+    edit_menu.add_emit(
+        "Redo",
+        Shortcut::Ctrl | Shortcut::Shift | 'z',
+        MenuFlag::MenuDivider,
+        sender,
+        Message::CrudRedo,
+    );
+    edit_menu.add_emit(
+        "Confirm delete",
+        Shortcut::None,
+        MenuFlag::Toggle | MenuFlag::Value | MenuFlag::MenuDivider,
+        sender,
+        Message::CrudToggleConfirm,
+    );
+    edit_menu.add_emit(
+        "Import...",
+        Shortcut::Ctrl | 'i',
+        MenuFlag::Normal,
+        sender,
+        Message::CrudImport,
+    );
+    edit_menu.add_emit(
+        "Export...",
+        Shortcut::Ctrl | 'e',
+        MenuFlag::Normal,
+        sender,
+        Message::CrudExport,
+    );
+
+    let mut filter_input = Input::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            CONTENT_X + WIDGET_PADDING + WIDGET_WIDTH * 2,
+            frame5.y() + frame5.h() + WIDGET_PADDING,
+        )
+        .with_label("Search:");
+    filter_input.set_trigger(CallbackTrigger::Changed);
+    filter_input.emit(sender, Message::CrudFilter);
+
+    // 匹配方式
+    let mut match_choice = Choice::default()
+        .with_size(WIDGET_WIDTH + WIDGET_PADDING, WIDGET_HEIGHT)
+        .right_of(&filter_input, WIDGET_PADDING);
+    match_choice.add_choice("Contains|Fuzzy");
+    match_choice.set_value(0);
+    match_choice.emit(sender, Message::CrudFilter);
+
+    // 排序的列、方向
+    let mut sort_choice = Choice::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            CONTENT_X + WIDGET_PADDING + WIDGET_WIDTH * 2,
+            filter_input.y() + filter_input.h() + WIDGET_PADDING,
+        )
+        .with_label("Sort by:");
+    sort_choice.add_choice("Surname|Name|Created");
+    sort_choice.set_value(0);
+    sort_choice.emit(sender, Message::CrudFilter);
+
+    let mut direction_choice = Choice::default()
+        .with_size(WIDGET_WIDTH + WIDGET_PADDING, WIDGET_HEIGHT)
+        .right_of(&sort_choice, WIDGET_PADDING);
+    direction_choice.add_choice("Ascending|Descending");
+    direction_choice.set_value(0);
+    direction_choice.emit(sender, Message::CrudFilter);
+
+    // 表格：Ctrl / Shift 多选，双击或 F2 直接编辑，Delete 删除选中的行
+    let mut person_table = PersonTable::new(
+        CONTENT_X + WIDGET_PADDING,
+        sort_choice.y() + sort_choice.height() + WIDGET_PADDING,
+        WIDGET_WIDTH * 3,
+        WIDGET_HEIGHT * 6,
+    );
+    person_table.on_event(move |event| {
+        sender.send(match event {
+            TableEvent::SelectionChanged => Message::CrudSelect,
+            TableEvent::CellEdited => Message::CrudCellEdited,
+            TableEvent::DeleteRequested => Message::CrudDelete,
+        })
+    });
+
+    // 显示了几条 / 一共几条
+    let mut count_frame = Frame::default()
+        .with_size(WIDGET_WIDTH * 3, WIDGET_HEIGHT)
+        .below_of(&*person_table, 0)
+        .with_align(Align::Inside | Align::Right);
+
+    let mut name_input = Input::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            person_table.x() + person_table.width() + WIDGET_PADDING + WIDGET_WIDTH,
+            person_table.y(),
+        )
+        .with_label("Name:");
+    name_input.set_trigger(CallbackTrigger::Changed);
+    name_input.emit(sender, Message::CrudFormChanged);
+
+    let mut surname_input = Input::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&name_input, WIDGET_PADDING)
+        .with_label("Surname:");
+    surname_input.set_trigger(CallbackTrigger::Changed);
+    surname_input.emit(sender, Message::CrudFormChanged);
+
+    let mut btn_clear = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&surname_input, WIDGET_PADDING)
+        .with_label("Clear");
+    btn_clear.emit(sender, Message::CrudClear);
+
+    let mut create_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            CONTENT_X + WIDGET_PADDING,
+            count_frame.y() + count_frame.height() + WIDGET_PADDING,
+        )
+        .with_label("Create");
+    create_button.emit(sender, Message::CrudCreate);
+    create_button.deactivate();
+
+    let mut update_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&create_button, WIDGET_PADDING)
+        .with_label("Update");
+    update_button.emit(sender, Message::CrudUpdate);
+    update_button.deactivate();
+
+    let mut delete_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&update_button, WIDGET_PADDING)
+        .with_label("Delete");
+    delete_button.emit(sender, Message::CrudDelete);
+    delete_button.deactivate();
+
+    // 启动时从文件加载；没有文件就用初始数据
+    let mut repository = load_repository(Store::new(STORE_FILE_PATH));
+    sender.send(Message::CrudFilter);
+
+    // 表单对应哪条记录，以及填进去时的值；和当前的值不同才算改过
+    let mut form_record: Option<u64> = None;
+    let mut form_baseline = (String::new(), String::new());
+
+    let mut history = History::default();
+    let mut confirm_delete = true;
+    update_edit_menu(&mut edit_menu, &history);
+
+    crud_group.end();
+    tool_groups.push(crud_group);
+
+    // 6. gallery
+    let gallery_group = tool_group();
+    draw_gallery(CONTENT_X, 0);
+    gallery_group.end();
+    tool_groups.push(gallery_group);
+
+    // 7. 计算器
+    let calculator_group = tool_group();
+    let calculator = Calculator::new(
+        CONTENT_X + WIDGET_PADDING,
+        WIDGET_PADDING,
+        CONTENT_WIDTH - WIDGET_PADDING * 2,
+        WINDOW_HEIGHT - WIDGET_PADDING * 2,
+    );
+    calculator_group.end();
+    tool_groups.push(calculator_group);
+
+    // 8. 计价接口
+    let pricing_group = tool_group();
+    let mut pricing_panel = PricingPanel::new(
+        CONTENT_X,
+        0,
+        CONTENT_WIDTH,
+        WINDOW_HEIGHT,
+        settings.pricing_url.as_deref().unwrap_or(DEFAULT_BASE_URL),
+        sender,
+    );
+    pricing_group.end();
+    tool_groups.push(pricing_group);
+
+    tool_wizard.end();
+
+    // 打开上次用的工具
+    let mut current_tool = settings
+        .tool
+        .as_deref()
+        .and_then(Tool::from_label)
+        .unwrap_or(Tool::Counter);
+    show_tool(
+        &mut tool_wizard,
+        &tool_groups,
+        &mut tool_browser,
+        &calculator,
+        current_tool,
+    );
+
+    wind.end();
+    wind.show();
+
+    // 窗口要在 wind.end() 之后创建，否则会变成 wind 的子窗口
+    let mut bookings_panel = BookingsPanel::new(sender);
+    bookings_panel.refresh(bookings.all(), &inventory);
+
+    if let Some(e) = theme_error {
+        alert_default(&format!(
+            "Could not read the custom themes {}: {}",
+            THEMES_FILE_PATH, e
+        ));
+    }
+
+    let mut i = 1;
+    but.set_callback(move |_| {
+        i = if i == 1 { 0 } else { 1 };
+
+        let msg = match i {
+            0 => "Hello World!",
+            1 => "Really Amazing",
+            _ => "BangBang!",
+        };
+
+        frame.set_label_color(Color::Red);
+        frame.set_color(Color::Blue);
+        frame.redraw();
+
+        frame.set_label(msg);
+    });
+
+    let mut value = 0;
+    button.set_callback(move |_| {
+        value += 1;
+        output.set_value(&format!("{}", value));
+    });
+
+    // app.run().unwrap();
+    while app.wait() {
+        // 所有的窗口消息，都會走到這
+        match receiver.recv() {
+            Some(Message::SchemeChanged) => {
+                let idx: usize = choice_scheme.value().try_into().unwrap();
+
+                let scheme = SchemeName::ALL[idx];
+                app.set_scheme(scheme.scheme());
+                app.redraw();
+
+                settings.scheme = Some(scheme);
+                save_settings(&settings);
+            }
+            Some(Message::ToolSelected) => {
+                // 点到列表的空白处什么都没选中，还是显示原来的
+                current_tool = usize::try_from(tool_browser.value() - 1)
+                    .ok()
+                    .and_then(|idx| Tool::ALL.get(idx).copied())
+                    .unwrap_or(current_tool);
+                show_tool(
+                    &mut tool_wizard,
+                    &tool_groups,
+                    &mut tool_browser,
+                    &calculator,
+                    current_tool,
+                );
+
+                settings.tool = Some(current_tool.label().to_string());
+                save_settings(&settings);
+            }
+            Some(Message::ThemeChanged) => {
+                let Some(theme) = usize::try_from(theme_choice.value())
+                    .ok()
+                    .and_then(|idx| themes.get(idx))
+                else {
+                    continue;
+                };
+
+                // 换主题时 scheme 也换成主题自己的
+                theme::apply(theme, theme.scheme);
+                choice_scheme.set_value(scheme_index(theme.scheme));
+                elapsed_progress.set_selection_color(theme.accent.color());
+                app.redraw();
+
+                settings.theme = Some(theme.name.clone());
+                settings.scheme = None;
+                save_settings(&settings);
+            }
+            Some(Message::TemperatureChanged(unit)) => {
+                let index = Unit::ALL.iter().position(|u| *u == unit).unwrap_or(0);
+                let input = &mut temperature_inputs[index];
+                let text = input.value();
+
+                temperature_source = if text.trim().is_empty() {
+                    mark_temperature(input, None);
+                    None
+                } else {
+                    match parse_temperature(&text, unit) {
+                        Ok(value) => {
+                            mark_temperature(input, None);
+                            Some((unit, value))
+                        }
+                        Err(e) => {
+                            mark_temperature(input, Some(&e.to_string()));
+                            None
+                        }
+                    }
+                };
+                fill_temperatures(
+                    &mut temperature_inputs,
+                    unit,
+                    temperature_source.map(|(_, value)| value),
+                    precision_spinner.value() as usize,
+                );
+            }
+            Some(Message::TemperaturePrecision) => {
+                if let Some((unit, value)) = temperature_source {
+                    fill_temperatures(
+                        &mut temperature_inputs,
+                        unit,
+                        Some(value),
+                        precision_spinner.value() as usize,
+                    );
+                }
+            }
+            Some(Message::BookingUpdate) => {
+                let route = usize::try_from(route_choice.value())
+                    .ok()
+                    .and_then(|i| routes.get(i));
+
+                let dates_valid;
+                let inbound_seats;
+                let start_date = get_date(&mut start_input, date_format).ok();
+                if choice.value() == one_way_flight_index {
+                    return_input.deactivate();
+                    return_pick_button.deactivate();
+                    inbound_choice.deactivate();
+                    inbound_choice.clear();
+                    inbound_codes.clear();
+
+                    dates_valid = start_date.is_some();
+                    inbound_seats = 1;
+                } else {
+                    return_input.activate();
+                    return_pick_button.activate();
+                    inbound_choice.activate();
+                    let return_date = get_date(&mut return_input, date_format).ok();
+
+                    dates_valid = matches!((start_date, return_date), (Some(s), Some(r)) if s <= r);
+                    inbound_seats = fill_flight_choice(
+                        &mut inbound_choice,
+                        &mut inbound_codes,
+                        &inventory,
+                        &bookings,
+                        route.map(|r| r.reverse()).as_ref(),
+                        return_date,
+                    );
+                }
+
+                let outbound_seats = fill_flight_choice(
+                    &mut outbound_choice,
+                    &mut outbound_codes,
+                    &inventory,
+                    &bookings,
+                    route,
+                    start_date,
+                );
+
+                if dates_valid && outbound_seats > 0 && inbound_seats > 0 {
+                    book_button.activate();
+                } else {
+                    book_button.deactivate();
+                }
+            }
+            Some(Message::BookingBook) => {
+                // Choice 选中的航班 + 输入框里的日期
+                let leg = |codes: &[String], flight: &Choice, date: &mut Input| {
+                    Some(Leg {
+                        code: codes.get(usize::try_from(flight.value()).ok()?)?.clone(),
+                        date: get_date(date, date_format).ok()?,
+                    })
+                };
+
+                let Some(outbound) = leg(&outbound_codes, &outbound_choice, &mut start_input)
+                else {
+                    continue;
+                };
+                let inbound = if choice.value() == one_way_flight_index {
+                    None
+                } else {
+                    let Some(inbound) = leg(&inbound_codes, &inbound_choice, &mut return_input)
+                    else {
+                        continue;
+                    };
+                    Some(inbound)
+                };
+
+                let now = Local::now().naive_local();
+                let msg = match bookings.book(&inventory, outbound, inbound, now) {
+                    Ok(booking) => match &booking.inbound {
+                        Some(inbound) => format!(
+                            "You have booked {}\nand {}.\nBooking reference: {}",
+                            inventory.describe(&booking.outbound),
+                            inventory.describe(inbound),
+                            booking.reference
+                        ),
+                        None => format!(
+                            "You have booked {}.\nBooking reference: {}",
+                            inventory.describe(&booking.outbound),
+                            booking.reference
+                        ),
+                    },
+                    Err(e) => format!("Could not book: {}", e),
+                };
+                alert_default(&msg);
+
+                bookings_panel.refresh(bookings.all(), &inventory);
+                sender.send(Message::BookingUpdate);
+            }
+            Some(Message::BookingPickStart) => {
+                // 过去的日期不能选
+                let today = Local::now().naive_local().date();
+                let max = today + chrono::Duration::days(BOOKING_HORIZON_DAYS);
+                let initial = get_date(&mut start_input, date_format).unwrap_or(today);
+                if let Some(date) = pick_date(&start_input, initial, Some(today), Some(max)) {
+                    start_input.set_value(&date_format.format(date));
+                    sender.send(Message::BookingUpdate);
+                }
+            }
+            Some(Message::BookingPickReturn) => {
+                // 回程不能早于去程
+                let today = Local::now().naive_local().date();
+                let max = today + chrono::Duration::days(BOOKING_HORIZON_DAYS);
+                let min = get_date(&mut start_input, date_format)
+                    .map(|start| start.max(today))
+                    .unwrap_or(today);
+                let initial = get_date(&mut return_input, date_format).unwrap_or(min);
+                if let Some(date) = pick_date(&return_input, initial, Some(min), Some(max)) {
+                    return_input.set_value(&date_format.format(date));
+                    sender.send(Message::BookingUpdate);
+                }
+            }
+            Some(Message::BookingFormatChanged) => {
+                if let Some(format) = usize::try_from(date_format_choice.value())
+                    .ok()
+                    .and_then(|i| DateFormat::ALL.get(i))
+                {
+                    date_format = *format;
+                }
+                sender.send(Message::BookingNormalize);
+            }
+            Some(Message::BookingNormalize) => {
+                normalize_date(&mut start_input, date_format);
+                normalize_date(&mut return_input, date_format);
+                sender.send(Message::BookingUpdate);
+            }
+            Some(Message::BookingShowList) => {
+                bookings_panel.show();
+            }
+            Some(Message::BookingSelect) => {
+                bookings_panel.update_buttons();
+            }
+            Some(Message::BookingCancel) => {
+                let Some(reference) = bookings_panel.selected_reference() else {
+                    continue;
+                };
+                let msg = format!("Cancel booking {}?", reference);
+                if choice2_default(&msg, "Keep", "Cancel booking", "") != Some(1) {
+                    continue;
+                }
+
+                if let Err(e) = bookings.cancel(&reference) {
+                    alert_default(&format!(
+                        "Could not cancel ({}): {}",
+                        bookings.path().display(),
+                        e
+                    ));
+                }
+                bookings_panel.refresh(bookings.all(), &inventory);
+                sender.send(Message::BookingUpdate);
+            }
+            Some(Message::TimerReset) => {
+                let now = Instant::now();
+                stopwatch.reset(now);
+                // 到时间停下来的，归零后重新开始
+                if let TimerStatus::Running = timer_status {
+                    stopwatch.start(now);
+                    ticker.resume();
+                }
+                show_elapsed(&mut elapsed_progress, &mut elapsed_frame, Duration::ZERO);
+            }
+            Some(Message::TimerChangeDuration) => {
+                elapsed_progress.set_maximum(duration_slider.value());
+
+                // 时间调长了，停在终点的计时器接着走
+                let now = Instant::now();
+                let duration = Duration::from_secs_f64(duration_slider.value());
+                if let TimerStatus::Running = timer_status {
+                    if stopwatch.elapsed(now) < duration {
+                        stopwatch.start(now);
+                        ticker.resume();
+                    }
+                }
+                sender.send(Message::TimerTick);
+            }
+            Some(Message::TimerTick) => {
+                let now = Instant::now();
+                let duration = Duration::from_secs_f64(duration_slider.value());
+                // 暂停、已经停在终点时，迟到的 tick 只刷新界面
+                if stopwatch.is_running() && stopwatch.elapsed(now) >= duration {
+                    stopwatch.stop_at(now, duration);
+                    ticker.pause();
+                }
+                show_elapsed(
+                    &mut elapsed_progress,
+                    &mut elapsed_frame,
+                    stopwatch.elapsed(now),
+                );
+            }
+            Some(Message::TimerPauseRunning) => {
+                let now = Instant::now();
+                match timer_status {
+                    TimerStatus::Running => {
+                        timer_status = TimerStatus::Pause;
+                        stopwatch.pause(now);
+                        ticker.pause();
+                        pause_running_button.set_label("Resume");
+                    }
+                    TimerStatus::Pause => {
+                        timer_status = TimerStatus::Running;
+                        if stopwatch.elapsed(now).as_secs_f64() < duration_slider.value() {
+                            stopwatch.start(now);
+                            ticker.resume();
+                        }
+                        pause_running_button.set_label("Pause");
+                    }
+                }
+            }
+            Some(Message::CrudClear) => {
+                surname_input.set_value("");
+                name_input.set_value("");
+
+                form_record = None;
+                form_baseline = (String::new(), String::new());
+                sender.send(Message::CrudFormChanged);
+            }
+            Some(Message::CrudCreate) => {
+                let Some((name, surname)) = form_values(&name_input, &surname_input) else {
+                    continue;
+                };
+                let result = repository.create(&name, &surname);
+                let Some(id) = report_store_error(&repository, result) else {
+                    continue;
+                };
+                if let Some(person) = repository.get(id) {
+                    history.record(Command::Create(person.clone()));
+                    update_edit_menu(&mut edit_menu, &history);
+                }
+                sender.send(Message::CrudFilter);
+
+                surname_input.set_value("");
+                name_input.set_value("");
+
+                form_record = None;
+                form_baseline = (String::new(), String::new());
+                sender.send(Message::CrudFormChanged);
+            }
+            Some(Message::CrudUpdate) => {
+                // 输入框只对应一条记录
+                let Some(id) = form_record else {
+                    continue;
+                };
+                let Some((name, surname)) = form_values(&name_input, &surname_input) else {
+                    continue;
+                };
+                let before = repository.get(id).cloned();
+                let result = repository.update(id, &name, &surname);
+                if report_store_error(&repository, result).is_none() {
+                    continue;
+                }
+                if let (Some(before), Some(after)) = (before, repository.get(id).cloned()) {
+                    history.record(Command::Update { before, after });
+                    update_edit_menu(&mut edit_menu, &history);
+                }
+                sender.send(Message::CrudFilter);
+
+                surname_input.set_value(&surname);
+                name_input.set_value(&name);
+                form_baseline = (name, surname);
+                sender.send(Message::CrudFormChanged);
+            }
+            Some(Message::CrudDelete) => {
+                let people: Vec<Person> = person_table
+                    .selected_ids()
+                    .iter()
+                    .filter_map(|id| repository.get(*id).cloned())
+                    .collect();
+                if people.is_empty() {
+                    continue;
+                }
+
+                if confirm_delete {
+                    let msg = match people.as_slice() {
+                        [person] => format!("Delete \"{}\"?", person.display_name()),
+                        _ => format!("Delete {} records?", people.len()),
+                    };
+                    if choice2_default(&msg, "Cancel", "Delete", "") != Some(1) {
+                        continue;
+                    }
+                }
+
+                // 一条的时候菜单里显示名字，多条的时候一次撤销
+                let command = if let [person] = people.as_slice() {
+                    let result = repository.delete(person.id);
+                    report_store_error(&repository, result).map(Command::Delete)
+                } else {
+                    let ids: Vec<u64> = people.iter().map(|p| p.id).collect();
+                    let result = repository.delete_many(&ids);
+                    report_store_error(&repository, result).map(|_| Command::DeleteMany(people))
+                };
+
+                if let Some(command) = command {
+                    history.record(command);
+                    update_edit_menu(&mut edit_menu, &history);
+
+                    sender.send(Message::CrudFilter);
+                }
+            }
+            Some(Message::CrudSelect) => {
+                let ids = person_table.selected_ids();
+                let selected = match ids[..] {
+                    [id] => repository.get(id),
+                    _ => None,
+                };
+
+                // 选中的记录没变、表单又改过时不重新填，正在输入的内容不会被刷新冲掉
+                let dirty = (name_input.value(), surname_input.value()) != form_baseline;
+                if selected.map(|p| p.id) != form_record || !dirty {
+                    let (name, surname) = selected
+                        .map(|p| (p.name.clone(), p.surname.clone()))
+                        .unwrap_or_default();
+                    surname_input.set_value(&surname);
+                    name_input.set_value(&name);
+
+                    form_record = selected.map(|p| p.id);
+                    form_baseline = (name, surname);
+                }
+                sender.send(Message::CrudFormChanged);
+
+                // 多选时显示要删几条
+                match ids.len() {
+                    0 => {
+                        delete_button.set_label("Delete");
+                        delete_button.deactivate();
+                    }
+                    1 => {
+                        delete_button.set_label("Delete");
+                        delete_button.activate();
+                    }
+                    n => {
+                        delete_button.set_label(&format!("Delete ({})", n));
+                        delete_button.activate();
+                    }
+                }
+            }
+            Some(Message::CrudCellEdited) => {
+                let Some(edit) = person_table.take_edit() else {
+                    continue;
+                };
+                let Some(before) = repository.get(edit.id).cloned() else {
+                    continue;
+                };
+
+                // 和表单用同样的规则；不合法就不保存，刷新后显示回原来的值
+                let rules = match edit.column {
+                    Column::Name => NAME_RULES,
+                    Column::Surname => SURNAME_RULES,
+                };
+                if let Err(e) = rules.validate(&edit.value) {
+                    alert_default(&e);
+                    sender.send(Message::CrudFilter);
+                    continue;
+                }
+                let value = edit.value.trim();
+
+                let (name, surname) = match edit.column {
+                    Column::Name => (value, before.surname.as_str()),
+                    Column::Surname => (before.name.as_str(), value),
+                };
+                let result = repository.update(edit.id, name, surname);
+                // 保存失败也要刷新，表格里显示回原来的值
+                if report_store_error(&repository, result).is_some() {
+                    if let Some(after) = repository.get(edit.id).cloned() {
+                        history.record(Command::Update { before, after });
+                        update_edit_menu(&mut edit_menu, &history);
+                    }
+                }
+                sender.send(Message::CrudFilter);
+            }
+            Some(Message::CrudFilter) => {
+                let query = Query {
+                    text: filter_input.value(),
+                    mode: match match_choice.value() {
+                        1 => MatchMode::Fuzzy,
+                        _ => MatchMode::Substring,
+                    },
+                    column: match sort_choice.value() {
+                        1 => SortColumn::Name,
+                        2 => SortColumn::Id,
+                        _ => SortColumn::Surname,
+                    },
+                    direction: match direction_choice.value() {
+                        1 => SortDirection::Descending,
+                        _ => SortDirection::Ascending,
+                    },
+                };
+
+                // 刷新之后，原来选中的记录如果还在，继续选中
+                let matches = filter_people(repository.all(), &query);
+                person_table.show_matches(&matches);
+
+                count_frame.set_label(&format!(
+                    "{} / {} shown",
+                    matches.len(),
+                    repository.all().len()
+                ));
+                sender.send(Message::CrudSelect);
+            }
+            Some(Message::CrudFormChanged) => {
+                // 没改过的表单不标红，刚清空时不会满屏报错
+                let dirty = (name_input.value(), surname_input.value()) != form_baseline;
+                let name_valid = check_field(&mut name_input, &NAME_RULES, dirty);
+                let surname_valid = check_field(&mut surname_input, &SURNAME_RULES, dirty);
+                let valid = name_valid && surname_valid;
+
+                if valid && dirty {
+                    create_button.activate();
+                } else {
+                    create_button.deactivate();
+                }
+
+                if valid && dirty && form_record.is_some() {
+                    update_button.activate();
+                } else {
+                    update_button.deactivate();
+                }
+            }
+            Some(Message::CrudUndo) => {
+                if let Some(result) = history.undo(&mut repository) {
+                    report_store_error(&repository, result);
+                    update_edit_menu(&mut edit_menu, &history);
+                    sender.send(Message::CrudFilter);
+                }
+            }
+            Some(Message::CrudRedo) => {
+                if let Some(result) = history.redo(&mut repository) {
+                    report_store_error(&repository, result);
+                    update_edit_menu(&mut edit_menu, &history);
+                    sender.send(Message::CrudFilter);
+                }
+            }
+            Some(Message::CrudToggleConfirm) => {
+                confirm_delete = edit_menu.at(2).map(|item| item.value()).unwrap_or(true);
+            }
+            Some(Message::CrudImport) => {
+                let Some(path) = choose_file(false) else {
+                    continue;
+                };
+                let Some(format) = Format::from_path(&path) else {
+                    alert_default("Only .csv and .json files can be imported.");
+                    continue;
+                };
+                let (table, mapping) = match ask_import_mapping(&path, format, repository.all()) {
+                    Ok(Some(selection)) => selection,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        alert_default(&format!("Could not read {}: {}", path.display(), e));
+                        continue;
+                    }
+                };
+
+                let plan = plan_import(&table, mapping, repository.all());
+                if !plan.records.is_empty() {
+                    let result = repository.create_many(&plan.records);
+                    let Some(created) = report_store_error(&repository, result) else {
+                        continue;
+                    };
+                    history.record(Command::Import(created));
+                    update_edit_menu(&mut edit_menu, &history);
+                    sender.send(Message::CrudFilter);
+                }
+                alert_default(&plan.report());
+            }
+            Some(Message::CrudExport) => {
+                let Some(mut path) = choose_file(true) else {
+                    continue;
+                };
+                // 没写扩展名就按 CSV 保存
+                let format = Format::from_path(&path).unwrap_or_else(|| {
+                    path.set_extension("csv");
+                    Format::Csv
+                });
+
+                match export(&path, format, repository.all()) {
+                    Ok(()) => alert_default(&format!(
+                        "Exported {} record(s) to {}",
+                        repository.all().len(),
+                        path.display()
+                    )),
+                    Err(e) => alert_default(&format!("Could not write {}: {}", path.display(), e)),
+                }
+            }
+            Some(Message::PricingEdited) => pricing_panel.count_ids(),
+            Some(Message::PricingSend) => {
+                // 发出去了才记住 base URL
+                if pricing_panel.send() {
+                    settings.pricing_url = Some(pricing_panel.base_url());
+                    save_settings(&settings);
+                }
+            }
+            Some(Message::PricingReceived) => pricing_panel.receive(),
+            // 窗口會有很多消息，這裡不需要 print！
+            None => (),
+        }
+    }
+}
+
+fn load_repository(store: Store<Person>) -> PersonRepository {
+    let people = match store.load_people() {
+        Ok(Some(people)) => people,
+        Ok(None) => [
+            ("Charles", "Babbage"),
+            ("Ada", "Lovelace"),
+            ("Alan", "Turing"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (name, surname))| Person {
+            id: i as u64 + 1,
+            name: name.to_string(),
+            surname: surname.to_string(),
+        })
+        .collect(),
+        Err(e) => {
+            // 损坏的文件挪开，避免下次保存时被覆盖
+            let msg = match store.quarantine() {
+                Ok(backup) => format!(
+                    "{} is corrupt ({}).\nIt was moved to {}, starting with an empty list.",
+                    store.path().display(),
+                    e,
+                    backup.display()
+                ),
+                Err(_) => format!(
+                    "{} could not be read ({}).\nStarting with an empty list.",
+                    store.path().display(),
+                    e
+                ),
+            };
+            alert_default(&msg);
+            vec![]
+        }
+    };
+
+    PersonRepository::new(store, people)
+}
+
+// 保存失败就弹框；成功时返回结果
+fn report_store_error<T>(repository: &PersonRepository, result: Result<T, CrudError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            alert_default(&format!(
+                "Could not save {}: {}",
+                repository.store().path().display(),
+                e
+            ));
+            None
+        }
+    }
+}
+
+// 菜单项显示将要撤销 / 重做的操作，没有就 disable
+fn update_edit_menu(menu: &mut MenuButton, history: &History) {
+    let items = [
+        (0, "Undo", history.can_undo(), history.undo_description()),
+        (1, "Redo", history.can_redo(), history.redo_description()),
+    ];
+
+    for (index, verb, enabled, description) in items {
+        let Some(mut item) = menu.at(index) else {
+            continue;
+        };
+
+        // 菜单的 label 里 @ 和 & 有特殊含义
+        let label = match description {
+            Some(description) => {
+                let description = description.replace('@', "@@").replace('&', "&&");
+                format!("{} {}", verb, description)
+            }
+            None => verb.to_string(),
+        };
+        item.set_label(&label);
+
+        // 没有可撤销、重做的步骤时变灰
+        if enabled {
+            item.activate();
+        } else {
+            item.deactivate();
+        }
+    }
+}
+
+// 导入、导出用的系统文件对话框；取消时返回 None
+fn choose_file(save: bool) -> Option<PathBuf> {
+    let mut chooser = NativeFileChooser::new(if save {
+        NativeFileChooserType::BrowseSaveFile
+    } else {
+        NativeFileChooserType::BrowseFile
+    });
+    chooser.set_filter("CSV\t*.csv\nJSON\t*.json");
+    if save {
+        chooser.set_option(NativeFileChooserOptions::SaveAsConfirm);
+        chooser.set_preset_file("contacts.csv");
+    }
+    chooser.show();
+
+    let path = chooser.filename();
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+// 导入前的预览：选哪一列是 name、哪一列是 surname；CSV 还要选第一行是不是列名
+// 下面列出会被跳过的行和原因，选列时跟着更新
+// 点 Cancel 或者关掉窗口时返回 None
+fn ask_import_mapping(
+    path: &Path,
+    format: Format,
+    existing: &[Person],
+) -> Result<Option<(Table, Mapping)>, String> {
+    let table = Rc::new(RefCell::new(read_table(path, format, true)?));
+    let existing = Rc::new(existing.to_vec());
+    let accepted = Rc::new(Cell::new(false));
+
+    let mut win = Window::default()
+        .with_size(500, 480)
+        .with_label("Import records");
+
+    let mut preview = Browser::default()
+        .with_pos(WIDGET_PADDING, WIDGET_PADDING)
+        .with_size(500 - WIDGET_PADDING * 2, 200);
+    preview.set_column_char('\t');
+
+    let mut header_check = CheckButton::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&preview, WIDGET_PADDING)
+        .with_label("First row is header");
+    header_check.set_checked(true);
+    // JSON 的列名就是 key，没得选
+    if format != Format::Csv {
+        header_check.deactivate();
+    }
+
+    let mut surname_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            WIDGET_PADDING + WIDGET_LABEL_WIDTH,
+            header_check.y() + header_check.h() + WIDGET_PADDING,
+        )
+        .with_label("Surname column:");
+
+    let mut name_choice = Choice::default()
+        .with_size(BOOKING_WIDGET_WIDTH, WIDGET_HEIGHT)
+        .below_of(&surname_choice, WIDGET_PADDING)
+        .with_label("Name column:");
+
+    let mut summary = Browser::default()
+        .with_pos(
+            WIDGET_PADDING,
+            name_choice.y() + name_choice.h() + WIDGET_PADDING,
+        )
+        .with_size(500 - WIDGET_PADDING * 2, 90);
+
+    let mut cancel_button = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(
+            win.w() - (WIDGET_WIDTH + WIDGET_PADDING) * 2,
+            win.h() - WIDGET_HEIGHT - WIDGET_PADDING,
+        )
+        .with_label("Cancel");
+
+    let mut import_button = ReturnButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .right_of(&cancel_button, WIDGET_PADDING)
+        .with_label("Import");
+
+    win.end();
+    win.make_modal(true);
+
+    fill_import_preview(
+        &table.borrow(),
+        &mut preview,
+        &mut surname_choice,
+        &mut name_choice,
+    );
+    fill_import_summary(
+        &table.borrow(),
+        selected_mapping(&surname_choice, &name_choice),
+        &existing,
+        &mut summary,
+    );
+
+    // 切换 "第一行是列名" 时重新读文件，列名跟着变
+    header_check.set_callback({
+        let table = table.clone();
+        let existing = existing.clone();
+        let path = path.to_path_buf();
+        let mut preview = preview.clone();
+        let mut surname_choice = surname_choice.clone();
+        let mut name_choice = name_choice.clone();
+        let mut summary = summary.clone();
+        move |check| match read_table(&path, format, check.is_checked()) {
+            Ok(next) => {
+                fill_import_preview(&next, &mut preview, &mut surname_choice, &mut name_choice);
+                let mapping = selected_mapping(&surname_choice, &name_choice);
+                fill_import_summary(&next, mapping, &existing, &mut summary);
+                *table.borrow_mut() = next;
+            }
+            Err(e) => alert_default(&format!("Could not read {}: {}", path.display(), e)),
+        }
+    });
+
+    // 换了列，重新检查一遍
+    for mut choice in [surname_choice.clone(), name_choice.clone()] {
+        choice.set_callback({
+            let table = table.clone();
+            let existing = existing.clone();
+            let surname_choice = surname_choice.clone();
+            let name_choice = name_choice.clone();
+            let mut summary = summary.clone();
+            move |_| {
+                let mapping = selected_mapping(&surname_choice, &name_choice);
+                fill_import_summary(&table.borrow(), mapping, &existing, &mut summary);
+            }
+        });
+    }
+
+    cancel_button.set_callback({
+        let mut win = win.clone();
+        move |_| win.hide()
+    });
+
+    import_button.set_callback({
+        let mut win = win.clone();
+        let accepted = accepted.clone();
+        move |_| {
+            accepted.set(true);
+            win.hide();
+        }
+    });
+
+    win.show();
+    while win.shown() {
+        app::wait();
+    }
+
+    if !accepted.get() {
+        return Ok(None);
+    }
+
+    let mapping = selected_mapping(&surname_choice, &name_choice);
+    let table = table.borrow().clone();
+    Ok(Some((table, mapping)))
+}
+
+fn selected_mapping(surname_choice: &Choice, name_choice: &Choice) -> Mapping {
+    // 第 0 项是 "(none)"
+    let column = |choice: &Choice| (choice.value() > 0).then(|| choice.value() as usize - 1);
+    Mapping {
+        name: column(name_choice),
+        surname: column(surname_choice),
+    }
+}
+
+// 按现在选的列试算一遍导入，列出会被跳过的行
+fn fill_import_summary(
+    table: &Table,
+    mapping: Mapping,
+    existing: &[Person],
+    summary: &mut Browser,
+) {
+    let plan = plan_import(table, mapping, existing);
+
+    summary.clear();
+    summary.add(&format!(
+        "@b@.{} record(s) will be imported, {} skipped",
+        plan.records.len(),
+        plan.skipped.len()
+    ));
+    for skipped in &plan.skipped {
+        summary.add(&format!("@C1@.row {}: {}", skipped.row, skipped.reason));
+    }
+}
+
+fn fill_import_preview(
+    table: &Table,
+    preview: &mut Browser,
+    surname_choice: &mut Choice,
+    name_choice: &mut Choice,
+) {
+    let width = preview.w() / table.columns.len().max(1) as i32;
+    preview.set_column_widths(&vec![width; table.columns.len()]);
+
+    // 列名加粗；"@." 之后的文本按原样显示
+    let line = |cells: &[String], bold: bool| {
+        cells
+            .iter()
+            .map(|cell| format!("{}@.{}", if bold { "@b" } else { "" }, cell))
+            .collect::<Vec<_>>()
+            .join("\t")
+    };
+
+    preview.clear();
+    preview.add(&line(&table.columns, true));
+    for row in table.rows.iter().take(IMPORT_PREVIEW_ROWS) {
+        preview.add(&line(row, false));
+    }
+    if table.rows.len() > IMPORT_PREVIEW_ROWS {
+        preview.add(&format!(
+            "@i@.... {} more row(s)",
+            table.rows.len() - IMPORT_PREVIEW_ROWS
+        ));
+    }
+
+    let guess = guess_mapping(&table.columns);
+    for (choice, guessed) in [(surname_choice, guess.surname), (name_choice, guess.name)] {
+        choice.clear();
+        choice.add_choice("(none)");
+        for (index, column) in table.columns.iter().enumerate() {
+            // 菜单的 label 里 / & | 有特殊含义
+            let label = column
+                .replace('/', "\\/")
+                .replace('&', "&&")
+                .replace('|', " ");
+            if label.trim().is_empty() {
+                choice.add_choice(&format!("Column {}", index + 1));
+            } else {
+                choice.add_choice(&label);
+            }
+        }
+        choice.set_value(guessed.map(|i| i as i32 + 1).unwrap_or(0));
+    }
+}
+
+// 和 get_date 一样，不合法的输入框标红；tooltip 显示原因
+fn check_field(input: &mut Input, rules: &FieldRules, highlight: bool) -> bool {
+    let result = rules.validate(&input.value());
+    match &result {
+        Err(e) if highlight => {
+            input.set_color(Color::Red);
+            input.set_tooltip(e);
+        }
+        _ => {
+            input.set_color(Color::BackGround2);
+            input.set_tooltip("");
+        }
+    }
+    input.redraw();
+    result.is_ok()
+}
+
+// 表单合法时返回去掉首尾空格的 (name, surname)
+fn form_values(name_input: &Input, surname_input: &Input) -> Option<(String, String)> {
+    NAME_RULES.validate(&name_input.value()).ok()?;
+    SURNAME_RULES.validate(&surname_input.value()).ok()?;
+    Some((
+        name_input.value().trim().to_string(),
+        surname_input.value().trim().to_string(),
+    ))
+}
+
+fn show_elapsed(progress: &mut Progress, frame: &mut Frame, elapsed: Duration) {
+    progress.set_value(elapsed.as_secs_f64());
+    frame.set_label(&format!("{:.1}s", elapsed.as_secs_f64()));
+}
+
+// 库存文件读不了就没有航班可订，其他功能照常
+fn load_inventory() -> Inventory {
+    Inventory::load(INVENTORY_FILE_PATH).unwrap_or_else(|e| {
+        alert_default(&format!(
+            "Could not read the flight inventory {}: {}",
+            INVENTORY_FILE_PATH, e
+        ));
+        Inventory::default()
+    })
+}
+
+// 文件不存在就是还没订过票；和 CRUD 一样，损坏的文件挪开，避免下次订票时被覆盖
+fn load_bookings() -> BookingStore {
+    let store = Store::new(BOOKINGS_FILE_PATH);
+    let bookings = match store.load() {
+        Ok(bookings) => bookings.unwrap_or_default(),
+        Err(e) => {
+            let msg = match store.quarantine() {
+                Ok(backup) => format!(
+                    "{} is corrupt ({}).\nIt was moved to {}, starting without bookings.",
+                    store.path().display(),
+                    e,
+                    backup.display()
+                ),
+                Err(_) => format!(
+                    "{} could not be read ({}).\nStarting without bookings.",
+                    store.path().display(),
+                    e
+                ),
+            };
+            alert_default(&msg);
+            vec![]
+        }
+    };
+    BookingStore::new(store, bookings)
+}
+
+// 把某天的航班填进 Choice，尽量保持原来选中的航班；返回选中的航班还剩几个座位
+fn fill_flight_choice(
+    choice: &mut Choice,
+    codes: &mut Vec<String>,
+    inventory: &Inventory,
+    bookings: &BookingStore,
+    route: Option<&Route>,
+    date: Option<NaiveDate>,
+) -> u32 {
+    let previous = usize::try_from(choice.value())
+        .ok()
+        .and_then(|i| codes.get(i).cloned());
+
+    choice.clear();
+    codes.clear();
+
+    let (Some(route), Some(date)) = (route, date) else {
+        return 0;
+    };
+
+    let mut seats = vec![];
+    for flight in inventory.flights_on(route, date) {
+        let left = bookings.seats_left(flight, date);
+        let label = match left {
+            0 => format!("{} {} (sold out)", flight.code, flight.departure),
+            n => format!("{} {} ({} seats left)", flight.code, flight.departure, n),
+        };
+        choice.add_choice(&label);
+        codes.push(flight.code.clone());
+        seats.push(left);
+    }
+
+    if codes.is_empty() {
+        choice.add_choice("(no flights)");
+        choice.set_value(0);
+        return 0;
+    }
+
+    // 默认选第一个还有座位的航班
+    let index = previous
+        .and_then(|code| codes.iter().position(|c| *c == code))
+        .or_else(|| seats.iter().position(|n| *n > 0))
+        .unwrap_or(0);
+    choice.set_value(index as i32);
+    seats[index]
+}
+
+// 不合法的日期标红，tooltip 说明原因
+fn get_date(input: &mut Input, format: DateFormat) -> Result<NaiveDate, DateError> {
+    let today = Local::now().naive_local().date();
+    let date = parse_date(&input.value(), today, format);
+    match &date {
+        Ok(_) => {
+            input.set_color(Color::BackGround2);
+            input.set_tooltip("");
+        }
+        Err(e) => {
+            input.set_color(Color::Red);
+            input.set_tooltip(&e.to_string());
+        }
+    }
+    input.redraw();
+    date
+}
+
+fn scheme_index(scheme: SchemeName) -> i32 {
+    SchemeName::ALL
+        .iter()
+        .position(|s| *s == scheme)
+        .unwrap_or(0) as i32
+}
+
+// 启动器右边每个工具的 Group，大小都一样，放在 Wizard 里
+fn tool_group() -> Group {
+    Group::new(CONTENT_X, 0, CONTENT_WIDTH, WINDOW_HEIGHT, None)
+}
+
+fn show_tool(
+    wizard: &mut Wizard,
+    groups: &[Group],
+    browser: &mut HoldBrowser,
+    calculator: &Calculator,
+    tool: Tool,
+) {
+    wizard.set_current_widget(&groups[tool.index()]);
+    browser.select(tool.index() as i32 + 1);
+    if tool == Tool::Calculator {
+        calculator.focus();
+    }
+}
+
+fn save_settings(settings: &Settings) {
+    if let Err(e) = settings.save(SETTINGS_FILE_PATH) {
+        alert_default(&format!(
+            "Could not save the settings to {}: {}",
+            SETTINGS_FILE_PATH, e
+        ));
+    }
+}
+
+// 温度输入不合法时标红，tooltip 说明原因
+fn mark_temperature(input: &mut Input, error: Option<&str>) {
+    match error {
+        Some(message) => {
+            input.set_color(Color::Red);
+            input.set_tooltip(message);
+        }
+        None => {
+            input.set_color(Color::BackGround2);
+            input.set_tooltip("");
+        }
+    }
+    input.redraw();
+}
+
+// 把 source 单位的 value 换算到其他几格；value 是 None 就清空
+// 只写别的格子，正在输入的那格不动；set_value 也不会触发 Changed，所以不会互相来回更新
+fn fill_temperatures(inputs: &mut [Input], source: Unit, value: Option<f64>, precision: usize) {
+    for (unit, input) in Unit::ALL.iter().zip(inputs.iter_mut()) {
+        if *unit == source {
+            continue;
+        }
+        let text = value
+            .map(|v| format_temperature(convert(v, source, *unit), precision))
+            .unwrap_or_default();
+        if input.value() != text {
+            input.set_value(&text);
+        }
+        mark_temperature(input, None);
+    }
+}
+
+// "tomorrow"、"17/10/2026" 之类的写法改成显示格式；不合法的保持原样
+fn normalize_date(input: &mut Input, format: DateFormat) {
+    let today = Local::now().naive_local().date();
+    if let Ok(date) = parse_date(&input.value(), today, format) {
+        let text = format.format(date);
+        if input.value() != text {
+            input.set_value(&text);
+        }
+    }
+}
+
+fn draw_gallery(x_pos: i32, y_pos: i32) {
+    let mut tab = Tabs::default()
+        .with_size(BOOKING_WIDGET_WIDTH + 20, BOOKING_WIDGET_WIDTH * 2)
+        .with_pos(x_pos + WIDGET_PADDING, y_pos + WIDGET_PADDING);
+
+    let mut grp1 = Flex::default_fill().with_label("Tab1\t\t").row();
+    let mut col = Pack::default();
+    grp1.fixed(&col, 200);
+    grp1.set_margin(10);
+    col.set_spacing(5);
+
+    let mut but1 = Button::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_label("Button");
+
+    // 单选框，必须要有一个 Group
+    let mut pack1: Pack = Pack::default().with_size(200, WIDGET_HEIGHT);
+    pack1.set_spacing(5);
+
+    let mut but11 = RadioRoundButton::default()
+        .with_size(55, WIDGET_HEIGHT)
+        .with_label("radio1");
+    let mut but12 = RadioRoundButton::default()
+        .with_size(55, WIDGET_HEIGHT)
+        .with_label("radio2");
+    let mut but13 = RadioRoundButton::default()
+        .with_size(55, WIDGET_HEIGHT)
+        .with_label("radio3");
+
+    pack1.end();
+    pack1.set_type(PackType::Horizontal);
+
+    // RoundButton, CheckButton 都是 LightButton 的 subclass，主打一个 on 的状态，没有 Group 的概念
+    let mut but21 = RoundButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_label("Round1");
+
+    // todo: 把 label 显示在左边
+    let mut but31 = CheckButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_pos(WIDGET_PADDING + WIDGET_LABEL_WIDTH, 0)
+        .with_align(Align::Left)
+        .with_label("Check1");
+
+    let mut but4 = LightButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_label("Light");
+
+    // 对应 回车键
+    let mut but6 = ReturnButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_label("Return");
+
+    let _dummy1 = Frame::default();
+    col.end();
+    col.resizable(&_dummy1);
+    grp1.end();
+
+    let mut grp2 = Flex::default_fill().with_label("Tab2\t\t").row();
+    let mut col2 = Pack::default();
+    grp2.fixed(&col2, 200);
+    grp2.set_margin(10);
+
+    col2.set_spacing(10);
+
+    let mut but5 = MenuButton::default()
+        .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+        .with_label("Menu");
+    but5.add_choice("Hello|World|From|Rust");
+
+    let mut chce = Choice::default().with_size(WIDGET_WIDTH, WIDGET_HEIGHT);
+    chce.add_choice("Hello|FLTK|From|Rust");
+
+    let mut inp = Input::default().with_size(WIDGET_WIDTH, WIDGET_HEIGHT);
+    let mut out = Output::default();
+    out.set_value("output");
+
+    col2.resizable(&out);
+    col2.end();
+    grp2.end();
+
+    let mut grp3 = Flex::default_fill().with_label("Tab3\t\t").row();
+    let mut col3 = Flex::default().column();
+    grp3.fixed(&col3, 200);
+    grp3.set_margin(10);
+
+    let mut btn_custom = MyCustomButton::new(50, "Click");
+    btn_custom.set_color(enums::Color::Cyan);
+
+    let mut out1 = Output::default();
+    out1.set_value(&clicks_text(btn_custom.num_clicks()));
+    col3.fixed(&out1, WIDGET_HEIGHT);
+
+    // 每次点击加多少
+    let mut step_row = Flex::default().row();
+    let step_frame = Frame::default().with_label("Step:");
+    let mut step_spinner = Spinner::default();
+    step_spinner.set_range(-10.0, 10.0);
+    step_spinner.set_step(1.0);
+    step_spinner.set_value(f64::from(btn_custom.step()));
+    step_row.fixed(&step_frame, WIDGET_WIDTH);
+    step_row.end();
+    col3.fixed(&step_row, WIDGET_HEIGHT);
+
+    let mut reset_button = StyledButton::pill(0, 0, "Reset");
+    reset_button.set_tooltip("Set the click counter back to 0");
+    col3.fixed(&*reset_button, WIDGET_HEIGHT);
+
+    let items = vec![
+        GalleryItem::new("button", "Button", &but1),
+        GalleryItem::new("radio1", "RadioRoundButton", &but11),
+        GalleryItem::new("radio2", "RadioRoundButton", &but12),
+        GalleryItem::new("radio3", "RadioRoundButton", &but13),
+        GalleryItem::new("round", "RoundButton", &but21),
+        GalleryItem::new("check", "CheckButton", &but31),
+        GalleryItem::new("light", "LightButton", &but4),
+        GalleryItem::new("return_button", "ReturnButton", &but6),
+        GalleryItem::new("menu", "MenuButton", &but5),
+        GalleryItem::new("choice", "Choice", &chce),
+        GalleryItem::new("input", "Input", &inp),
+        GalleryItem::new("output", "Output", &out),
+        GalleryItem::new("custom", "MyCustomButton", &*btn_custom),
+        GalleryItem::new("clicks", "Output", &out1),
+    ];
+
+    // 每个 widget 的事件和回调都记到事件窗口里
+    let log = EventLog::new();
+    let on_off = |on: bool| if on { "on" } else { "off" };
+
+    log.watch("button", &mut but1);
+    but1.set_callback({
+        let mut log = log.clone();
+        move |_| log.log("button", "callback", "")
+    });
+
+    for (name, radio) in [
+        ("radio1", &mut but11),
+        ("radio2", &mut but12),
+        ("radio3", &mut but13),
+    ] {
+        log.watch(name, radio);
+        // 同组的其他 radio 被取消时没有回调
+        radio.set_callback({
+            let mut log = log.clone();
+            move |b| log.log(name, "callback", on_off(b.value()))
+        });
+    }
+
+    log.watch("round", &mut but21);
+    but21.set_callback({
+        let mut log = log.clone();
+        move |b| log.log("round", "callback", on_off(b.value()))
+    });
+    log.watch("check", &mut but31);
+    but31.set_callback({
+        let mut log = log.clone();
+        move |b| log.log("check", "callback", on_off(b.is_checked()))
+    });
+    log.watch("light", &mut but4);
+    but4.set_callback({
+        let mut log = log.clone();
+        move |b| log.log("light", "callback", on_off(b.value()))
+    });
+    log.watch("return_button", &mut but6);
+    but6.set_callback({
+        let mut log = log.clone();
+        move |_| log.log("return_button", "callback", "")
+    });
+
+    // 菜单记下选中的是第几项、哪一项
+    log.watch("menu", &mut but5);
+    but5.set_callback({
+        let mut log = log.clone();
+        move |m| {
+            let item = format!("{} {}", m.value(), m.choice().unwrap_or_default());
+            log.log("menu", "callback", &item)
+        }
+    });
+    log.watch("choice", &mut chce);
+    chce.set_callback({
+        let mut log = log.clone();
+        move |c| {
+            let item = format!("{} {}", c.value(), c.choice().unwrap_or_default());
+            log.log("choice", "callback", &item)
+        }
+    });
+
+    // 每输入一个字都回调一次
+    log.watch("input", &mut inp);
+    inp.set_trigger(CallbackTrigger::Changed);
+    inp.set_callback({
+        let mut log = log.clone();
+        move |i| log.log("input", "callback", &i.value())
+    });
+    log.watch("output", &mut out);
+
+    // MyCustomButton 的回调用来计数，下面用 on_change 记录
+    log.watch("clicks", &mut out1);
+
+    col3.end();
+    grp3.end();
+
+    tab.end();
+    tab.auto_layout();
+
+    // 属性面板和 Frames 窗口共用一份 FrameType 列表
+    let frames = Rc::new(frame_types());
+
+    // 右边是属性面板
+    let inspector = Inspector::new(
+        tab.x() + tab.w() + WIDGET_PADDING,
+        tab.y(),
+        INSPECTOR_WIDTH,
+        tab.h(),
+        items,
+        frames.clone(),
+    );
+    inspector.follow_clicks(&mut tab);
+
+    // 计数变了，订阅的 widget 自己更新
+    btn_custom.on_change(move |clicks| out1.set_value(&clicks_text(clicks)));
+    btn_custom.on_change({
+        let mut log = log.clone();
+        move |clicks| log.log("custom", "on_change", &clicks.to_string())
+    });
+
+    step_spinner.set_callback({
+        let mut btn_custom = btn_custom.clone();
+        move |s| btn_custom.set_step(s.value() as i32)
+    });
+    reset_button.set_callback(move |_| btn_custom.reset());
+
+    // 事件记录和 FrameType 预览是单独的窗口，要看的时候再打开
+    let mut events_button = Button::default()
+        .with_size(WIDGET_WIDTH + WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .below_of(&tab, WIDGET_PADDING)
+        .with_label("Events...");
+    events_button.set_callback(move |_| log.clone().show());
+
+    let mut frames_button = Button::default()
+        .with_size(WIDGET_WIDTH + WIDGET_PADDING * 2, WIDGET_HEIGHT)
+        .right_of(&events_button, WIDGET_PADDING)
+        .with_label("Frames...");
+    frames_button.set_callback(move |_| show_frame_browser(&frames));
+}
+
+// 输出框里显示的计数，开始时和每次变化都用它
+fn clicks_text(clicks: i32) -> String {
+    format!("Got {}", clicks)
+}
+
+#[derive(Clone)]
+struct MyCustomButton {
+    inner: Widget,
+    counter: Rc<ClickCounter>,
+}
+
+impl MyCustomButton {
+    // our constructor
+    // 外观和键盘操作交给 StyledButton；它的回调用来计数，外面要用 on_change 订阅
+    pub fn new(radius: i32, label: &str) -> Self {
+        let inner = StyledButton::round(radius * 2, label);
+
+        let mut button = Self {
+            inner: inner.as_base_widget(),
+            counter: Rc::new(ClickCounter::default()),
+        };
+
+        // 按钮上显示计数；先订阅，排在外面的订阅者前面
+        button.counter.on_change({
+            let mut inner = button.inner.clone();
+            move |clicks| {
+                inner.set_label(&format!("Clicked: {}", clicks));
+                inner.redraw();
+            }
+        });
+
+        button.inner.set_callback({
+            let counter = button.counter.clone();
+            move |_| counter.click()
+        });
+
+        button
+    }
+
+    // get the times our button was clicked
+    pub fn num_clicks(&self) -> i32 {
+        self.counter.clicks()
+    }
+
+    pub fn step(&self) -> i32 {
+        self.counter.step()
+    }
+
+    pub fn set_step(&mut self, step: i32) {
+        self.counter.set_step(step);
+    }
+
+    // 计数归零，也会通知订阅者
+    pub fn reset(&mut self) {
+        self.counter.reset();
+    }
+
+    // 订阅计数的变化
+    pub fn on_change(&mut self, handler: impl FnMut(i32) + 'static) {
+        self.counter.on_change(handler);
+    }
+}
+
+// Extend widget::Widget via the member `inner` and add other initializers and constructors
+widget_extends!(MyCustomButton, widget::Widget, inner);
@@ -0,0 +1,424 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use fltk::{
+    app, draw,
+    enums::{Align, CallbackTrigger, Color, Event, Font, FrameType, Key},
+    input::Input,
+    prelude::*,
+    table::{TableContext, TableRow, TableRowSelectFlag, TableRowSelectMode},
+    widget_extends,
+};
+
+use crate::filter::Match;
+
+// CRUD 的表格：列头、可以拖动调整列宽、多选、双击或 F2 直接在格子里编辑
+
+const HEADERS: [&str; 2] = ["Surname", "Name"];
+const CELL_PADDING: i32 = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Column {
+    Surname,
+    Name,
+}
+
+impl Column {
+    fn from_index(col: i32) -> Column {
+        match col {
+            1 => Column::Name,
+            _ => Column::Surname,
+        }
+    }
+}
+
+// 格子里改完、按了回车的内容，由 main 写回 repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellEdit {
+    pub id: u64,
+    pub column: Column,
+    pub value: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TableEvent {
+    SelectionChanged,
+    CellEdited,
+    DeleteRequested,
+}
+
+// 表格的一行，带上搜索的高亮
+#[derive(Debug, Clone)]
+struct Row {
+    id: u64,
+    cells: [String; 2],
+    hits: [bool; 2],
+}
+
+type EventHandler = Rc<RefCell<Box<dyn FnMut(TableEvent)>>>;
+
+#[derive(Clone)]
+pub struct PersonTable {
+    table: TableRow,
+    editor: Input,
+    rows: Rc<RefCell<Vec<Row>>>,
+    // 键盘导航的当前格子 (row, col)
+    cursor: Rc<Cell<(i32, i32)>>,
+    // 正在编辑的格子
+    editing: Rc<Cell<Option<(i32, i32)>>>,
+    pending_edit: Rc<RefCell<Option<CellEdit>>>,
+    on_event: EventHandler,
+}
+
+impl PersonTable {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        let mut table = TableRow::new(x, y, w, h, None);
+        table.set_type(TableRowSelectMode::Multi);
+        table.set_rows(0);
+        table.set_cols(HEADERS.len() as i32);
+        table.set_col_header(true);
+        table.set_col_resize(true);
+        table.set_col_width_all((w - 4) / HEADERS.len() as i32);
+        table.set_row_height_all(22);
+        table.set_visible_focus(true);
+
+        // 编辑框是表格的子 widget，平时藏起来，编辑时盖在格子上
+        let mut editor = Input::default();
+        editor.set_frame(FrameType::BorderBox);
+        editor.set_trigger(CallbackTrigger::EnterKeyAlways);
+        editor.hide();
+        table.end();
+
+        let mut person_table = Self {
+            table,
+            editor,
+            rows: Rc::default(),
+            cursor: Rc::new(Cell::new((0, 0))),
+            editing: Rc::default(),
+            pending_edit: Rc::default(),
+            on_event: Rc::new(RefCell::new(Box::new(|_| {}))),
+        };
+        person_table.install_draw();
+        person_table.install_handlers();
+        person_table
+    }
+
+    pub fn on_event(&mut self, handler: impl FnMut(TableEvent) + 'static) {
+        *self.on_event.borrow_mut() = Box::new(handler);
+    }
+
+    // 换成新的搜索结果，原来选中的 id 还在的话继续选中
+    pub fn show_matches(&mut self, matches: &[Match]) {
+        let selected = self.selected_ids();
+        self.cancel_edit();
+
+        *self.rows.borrow_mut() = matches
+            .iter()
+            .map(|m| Row {
+                id: m.person.id,
+                cells: [m.person.surname.clone(), m.person.name.clone()],
+                hits: [m.surname_hit, m.name_hit],
+            })
+            .collect();
+
+        let count = self.rows.borrow().len() as i32;
+        self.table.set_rows(count);
+        self.select_ids(&selected);
+
+        let (row, col) = self.cursor.get();
+        self.cursor.set((row.clamp(0, (count - 1).max(0)), col));
+        self.table.redraw();
+    }
+
+    pub fn selected_ids(&self) -> Vec<u64> {
+        let mut table = self.table.clone();
+        self.rows
+            .borrow()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| table.row_selected(*index as i32))
+            .map(|(_, row)| row.id)
+            .collect()
+    }
+
+    pub fn select_ids(&mut self, ids: &[u64]) {
+        let _ = self.table.select_all_rows(TableRowSelectFlag::Deselect);
+        let rows = self.rows.borrow();
+        for (index, row) in rows.iter().enumerate() {
+            if ids.contains(&row.id) {
+                let _ = self
+                    .table
+                    .select_row(index as i32, TableRowSelectFlag::Select);
+            }
+        }
+    }
+
+    pub fn take_edit(&self) -> Option<CellEdit> {
+        self.pending_edit.borrow_mut().take()
+    }
+
+    fn install_draw(&mut self) {
+        let rows = self.rows.clone();
+        let cursor = self.cursor.clone();
+
+        self.table
+            .draw_cell(move |t, ctx, row, col, x, y, w, h| match ctx {
+                TableContext::StartPage => draw::set_font(Font::Helvetica, app::font_size()),
+                TableContext::ColHeader => {
+                    draw::push_clip(x, y, w, h);
+                    draw::draw_box(FrameType::ThinUpBox, x, y, w, h, t.col_header_color());
+                    draw::set_draw_color(Color::Black);
+                    draw::set_font(Font::HelveticaBold, app::font_size());
+                    draw::draw_text2(HEADERS[col as usize], x, y, w, h, Align::Center);
+                    draw::pop_clip();
+                }
+                TableContext::Cell => {
+                    let rows = rows.borrow();
+                    let Some(data) = rows.get(row as usize) else {
+                        return;
+                    };
+                    let selected = t.row_selected(row);
+
+                    draw::push_clip(x, y, w, h);
+                    let background = if selected {
+                        t.selection_color()
+                    } else {
+                        Color::BackGround2
+                    };
+                    draw::draw_rect_fill(x, y, w, h, background);
+
+                    // 匹配的列加粗、红色
+                    let hit = data.hits[col as usize];
+                    let (font, text_color) = match (hit, selected) {
+                        (true, _) => (Font::HelveticaBold, Color::Red),
+                        (false, true) => (Font::Helvetica, Color::White),
+                        (false, false) => (Font::Helvetica, Color::Black),
+                    };
+                    draw::set_font(font, app::font_size());
+                    draw::set_draw_color(text_color);
+                    // draw_text2 会把 @ 当成符号
+                    draw::draw_text2(
+                        &data.cells[col as usize].replace('@', "@@"),
+                        x + CELL_PADDING,
+                        y,
+                        w - CELL_PADDING * 2,
+                        h,
+                        Align::Left,
+                    );
+
+                    draw::set_draw_color(Color::Light2);
+                    draw::draw_rect(x, y, w, h);
+
+                    // 键盘导航的当前格子
+                    if t.has_focus() && cursor.get() == (row, col) {
+                        draw::set_draw_color(Color::Foreground);
+                        draw::set_line_style(draw::LineStyle::Dot, 1);
+                        draw::draw_rect(x + 1, y + 1, w - 2, h - 2);
+                        draw::set_line_style(draw::LineStyle::Solid, 0);
+                    }
+                    draw::pop_clip();
+                }
+                _ => (),
+            });
+    }
+
+    fn install_handlers(&mut self) {
+        // 鼠标选中的行有变化
+        self.table.set_callback({
+            let this = self.clone();
+            move |t| {
+                if t.callback_context() == TableContext::Cell {
+                    this.cursor.set((t.callback_row(), t.callback_col()));
+                    this.emit(TableEvent::SelectionChanged);
+                }
+            }
+        });
+
+        self.table.handle({
+            let mut this = self.clone();
+            move |t, ev| match ev {
+                Event::Push => {
+                    // 这时 TableRow 自己还没处理这次点击，callback_row / callback_col
+                    // 还是上一次的；按鼠标位置算出点到的格子
+                    let cell = match t.cursor2rowcol() {
+                        Some((TableContext::Cell, row, col, _)) => (row, col),
+                        _ => {
+                            // 点到别处 (例如拖列宽)，先结束编辑
+                            this.commit_edit();
+                            return false;
+                        }
+                    };
+                    let _ = t.take_focus();
+                    if this.editing.get() != Some(cell) {
+                        this.commit_edit();
+                    }
+                    if app::event_clicks() {
+                        this.cursor.set(cell);
+                        this.start_edit();
+                        return true;
+                    }
+                    false
+                }
+                Event::KeyDown => this.handle_key(),
+                Event::Focus | Event::Unfocus => {
+                    t.redraw();
+                    false
+                }
+                _ => false,
+            }
+        });
+
+        self.editor.set_callback({
+            let mut this = self.clone();
+            move |_| {
+                this.commit_edit();
+                let _ = this.table.take_focus();
+            }
+        });
+
+        // Esc 放弃编辑；焦点离开编辑框时保存
+        self.editor.handle({
+            let mut this = self.clone();
+            move |_, ev| match ev {
+                Event::KeyDown if app::event_key() == Key::Escape => {
+                    this.cancel_edit();
+                    let _ = this.table.take_focus();
+                    true
+                }
+                Event::Unfocus => {
+                    this.commit_edit();
+                    false
+                }
+                _ => false,
+            }
+        });
+    }
+
+    // 方向键移动当前格子；Shift 加方向键扩大选择；Enter / F2 编辑；Delete 删除选中的行
+    fn handle_key(&mut self) -> bool {
+        let count = self.rows.borrow().len() as i32;
+        let (row, col) = self.cursor.get();
+        let shift = app::is_event_shift();
+
+        let key = app::event_key();
+        let target = match key {
+            Key::Up => (row - 1, col),
+            Key::Down => (row + 1, col),
+            Key::Left => (row, col - 1),
+            Key::Right => (row, col + 1),
+            Key::Home => (0, col),
+            Key::End => (count - 1, col),
+            Key::Enter | Key::KPEnter | Key::F2 => {
+                self.start_edit();
+                return true;
+            }
+            Key::Delete => {
+                self.emit(TableEvent::DeleteRequested);
+                return true;
+            }
+            _ if app::is_event_ctrl() && key == Key::from_char('a') => {
+                let _ = self.table.select_all_rows(TableRowSelectFlag::Select);
+                self.table.redraw();
+                self.emit(TableEvent::SelectionChanged);
+                return true;
+            }
+            _ => return false,
+        };
+
+        if count == 0 {
+            return true;
+        }
+        let target = (
+            target.0.clamp(0, count - 1),
+            target.1.clamp(0, HEADERS.len() as i32 - 1),
+        );
+        self.cursor.set(target);
+
+        if target.0 != row || !shift {
+            if !shift {
+                let _ = self.table.select_all_rows(TableRowSelectFlag::Deselect);
+            }
+            let _ = self.table.select_row(target.0, TableRowSelectFlag::Select);
+            self.emit(TableEvent::SelectionChanged);
+        }
+        self.scroll_to(target.0);
+        self.table.redraw();
+        true
+    }
+
+    fn scroll_to(&mut self, row: i32) {
+        let (top, bottom, _, _) = self.table.visible_cells().unwrap_or((0, 0, 0, 0));
+        if row <= top {
+            self.table.set_row_position(row);
+        } else if row >= bottom {
+            self.table.set_row_position(row - (bottom - top) + 1);
+        }
+    }
+
+    fn start_edit(&mut self) {
+        let (row, col) = self.cursor.get();
+        let Some(text) = self
+            .rows
+            .borrow()
+            .get(row as usize)
+            .map(|r| r.cells[col as usize].clone())
+        else {
+            return;
+        };
+
+        self.scroll_to(row);
+        let Some((x, y, w, h)) = self.table.find_cell(TableContext::Cell, row, col) else {
+            return;
+        };
+
+        self.editing.set(Some((row, col)));
+        self.editor.resize(x, y, w, h);
+        self.editor.set_value(&text);
+        self.editor.show();
+        let _ = self.editor.take_focus();
+        let _ = self.editor.set_position(text.len() as i32);
+        let _ = self.editor.set_mark(0);
+        self.table.redraw();
+    }
+
+    fn commit_edit(&mut self) {
+        let Some((row, col)) = self.editing.take() else {
+            return;
+        };
+        self.editor.hide();
+
+        let value = self.editor.value();
+        let edit = self.rows.borrow().get(row as usize).and_then(|r| {
+            (r.cells[col as usize] != value).then(|| CellEdit {
+                id: r.id,
+                column: Column::from_index(col),
+                value,
+            })
+        });
+
+        self.table.redraw();
+        if let Some(edit) = edit {
+            *self.pending_edit.borrow_mut() = Some(edit);
+            self.emit(TableEvent::CellEdited);
+        }
+    }
+
+    fn cancel_edit(&mut self) {
+        if self.editing.take().is_some() {
+            self.editor.hide();
+            self.table.redraw();
+        }
+    }
+
+    fn emit(&self, event: TableEvent) {
+        // 事件处理里可能又回到表格 (例如 set_rows)，借用不能跨过回调
+        if let Ok(mut handler) = self.on_event.try_borrow_mut() {
+            handler(event);
+        }
+    }
+}
+
+// 可以像 TableRow 一样摆放位置
+widget_extends!(PersonTable, TableRow, table);
@@ -0,0 +1,246 @@
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Instant;
+
+use fltk::{
+    app::Sender,
+    button::Button,
+    enums::{Align, CallbackTrigger, Color, Font},
+    frame::Frame,
+    input::{Input, MultilineInput},
+    prelude::*,
+    text::{TextBuffer, TextDisplay},
+};
+use hello_fltk::pricing::{
+    self, build_request, parse_id_list, pretty_json, PricingError, PricingReply,
+};
+
+use crate::{Message, WIDGET_HEIGHT, WIDGET_LABEL_WIDTH, WIDGET_PADDING, WIDGET_WIDTH};
+
+// 计价接口的面板：填参数，SO 编号整列粘贴进来，后台线程发请求，回来显示状态、耗时和 JSON
+
+const UUID_INPUT_WIDTH: i32 = 160;
+
+type PricingResult = Result<PricingReply, PricingError>;
+
+pub struct PricingPanel {
+    url_input: Input,
+    company_input: Input,
+    operator_input: Input,
+    template_input: Input,
+    uuid_input: MultilineInput,
+    count_frame: Frame,
+    send_button: Button,
+    status_frame: Frame,
+    response_buffer: TextBuffer,
+    sender: Sender<Message>,
+    // 后台线程把结果放这里，再发 PricingReceived 叫醒主循环
+    reply_sender: mpsc::Sender<PricingResult>,
+    replies: Receiver<PricingResult>,
+    // 正在发的请求是什么时候开始的；没有在发就是 None
+    started: Option<Instant>,
+}
+
+impl PricingPanel {
+    // 在当前的 Group 里创建，占 (x, y, w, h)
+    pub fn new(x: i32, y: i32, w: i32, h: i32, base_url: &str, sender: Sender<Message>) -> Self {
+        let field_x = x + WIDGET_PADDING + WIDGET_LABEL_WIDTH;
+
+        let mut url_input = Input::default()
+            .with_size(x + w - WIDGET_PADDING - field_x, WIDGET_HEIGHT)
+            .with_pos(field_x, y + WIDGET_PADDING)
+            .with_label("Base URL:");
+        url_input.set_value(base_url);
+        url_input.set_tooltip("Only http:// is supported, e.g. http://127.0.0.1:8002");
+
+        let mut company_input = Input::default()
+            .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+            .below_of(&url_input, WIDGET_PADDING)
+            .with_label("Company code:");
+        company_input.set_tooltip("companyCode");
+
+        let mut operator_input = Input::default()
+            .with_size(WIDGET_WIDTH * 2, WIDGET_HEIGHT)
+            .right_of(&company_input, WIDGET_LABEL_WIDTH)
+            .with_label("Operator:");
+        operator_input.set_tooltip("operator");
+
+        let mut template_input = Input::default()
+            .with_size(x + w - WIDGET_PADDING - field_x, WIDGET_HEIGHT)
+            .below_of(&company_input, WIDGET_PADDING)
+            .with_label("Template IDs:");
+        template_input.set_tooltip("bcmTmplIdList, separated by commas or spaces");
+
+        let mut send_button = Button::default()
+            .with_size(WIDGET_WIDTH, WIDGET_HEIGHT)
+            .below_of(&template_input, WIDGET_PADDING)
+            .with_label("Send");
+        send_button.emit(sender, Message::PricingSend);
+
+        let mut status_frame = Frame::default()
+            .with_size(
+                x + w - WIDGET_PADDING * 2 - send_button.x() - send_button.w(),
+                WIDGET_HEIGHT,
+            )
+            .right_of(&send_button, WIDGET_PADDING)
+            .with_align(Align::Inside | Align::Left);
+        status_frame.set_label("Not sent yet");
+
+        // 下面左边是 SO 编号，右边是返回的内容；标签在上面
+        let list_y = send_button.y() + send_button.h() + WIDGET_PADDING + WIDGET_HEIGHT;
+        let list_h = y + h - list_y - WIDGET_HEIGHT - WIDGET_PADDING;
+
+        let mut uuid_input = MultilineInput::default()
+            .with_size(UUID_INPUT_WIDTH, list_h)
+            .with_pos(x + WIDGET_PADDING, list_y)
+            .with_align(Align::TopLeft)
+            .with_label("SO head UUIDs:");
+        uuid_input.set_tooltip("soHeadUuidList, one per line; paste a column from a spreadsheet");
+        uuid_input.set_trigger(CallbackTrigger::Changed);
+        uuid_input.emit(sender, Message::PricingEdited);
+
+        let count_frame = Frame::default()
+            .with_size(UUID_INPUT_WIDTH, WIDGET_HEIGHT)
+            .below_of(&uuid_input, 0)
+            .with_align(Align::Inside | Align::Left);
+
+        let response_x = uuid_input.x() + uuid_input.w() + WIDGET_PADDING;
+        let mut response_display = TextDisplay::default()
+            .with_size(x + w - WIDGET_PADDING - response_x, list_h + WIDGET_HEIGHT)
+            .with_pos(response_x, list_y)
+            .with_align(Align::TopLeft)
+            .with_label("Response:");
+        let response_buffer = TextBuffer::default();
+        response_display.set_buffer(response_buffer.clone());
+        response_display.set_text_font(Font::Courier);
+
+        let (reply_sender, replies) = mpsc::channel();
+        let mut panel = Self {
+            url_input,
+            company_input,
+            operator_input,
+            template_input,
+            uuid_input,
+            count_frame,
+            send_button,
+            status_frame,
+            response_buffer,
+            sender,
+            reply_sender,
+            replies,
+            started: None,
+        };
+        panel.count_ids();
+        panel
+    }
+
+    pub fn base_url(&self) -> String {
+        self.url_input.value().trim().to_string()
+    }
+
+    // 粘贴、修改编号时，马上显示有几个，或者哪一行不对
+    pub fn count_ids(&mut self) {
+        let text = self.uuid_input.value();
+        let (label, color) = if text.trim().is_empty() {
+            ("No SO head UUIDs".to_string(), Color::Foreground)
+        } else {
+            match parse_id_list("SO head UUIDs", &text) {
+                Ok(ids) => (format!("{} SO head UUIDs", ids.len()), Color::Foreground),
+                Err(PricingError::InvalidId { line, .. }) => {
+                    (format!("Line {} is not a number", line), Color::Red)
+                }
+                Err(e) => (e.to_string(), Color::Red),
+            }
+        };
+        self.count_frame.set_label(&label);
+        self.count_frame.set_label_color(color);
+        self.count_frame.redraw();
+    }
+
+    // 参数不对就不发，返回 false；发出去之后按钮变灰，等结果回来
+    pub fn send(&mut self) -> bool {
+        if self.started.is_some() {
+            return false;
+        }
+
+        let request = match build_request(
+            &self.uuid_input.value(),
+            &self.template_input.value(),
+            &self.company_input.value(),
+            &self.operator_input.value(),
+        ) {
+            Ok(request) => request,
+            Err(e) => {
+                self.set_status(&e.to_string(), Color::Red);
+                return false;
+            }
+        };
+        let base_url = self.base_url();
+        if let Err(e) = pricing::endpoint(&base_url) {
+            self.set_status(&e.to_string(), Color::Red);
+            return false;
+        }
+
+        self.set_status(
+            &format!(
+                "Sending {} SO head UUIDs ...",
+                request.so_head_uuid_list.len()
+            ),
+            Color::Foreground,
+        );
+        self.send_button.deactivate();
+        self.response_buffer.set_text("");
+        self.started = Some(Instant::now());
+
+        let reply_sender = self.reply_sender.clone();
+        let sender = self.sender;
+        thread::spawn(move || {
+            let _ = reply_sender.send(pricing::send(&base_url, &request));
+            sender.send(Message::PricingReceived);
+        });
+        true
+    }
+
+    pub fn receive(&mut self) {
+        let Ok(result) = self.replies.try_recv() else {
+            return;
+        };
+        let elapsed = self.started.take().map(|s| s.elapsed()).unwrap_or_default();
+        self.send_button.activate();
+
+        match result {
+            Ok(reply) => {
+                let response = reply.response;
+                let color = if (200..300).contains(&response.status) {
+                    Color::DarkGreen
+                } else {
+                    Color::Red
+                };
+                self.set_status(
+                    &format!(
+                        "{} {}  {} ms  {} bytes",
+                        response.status,
+                        response.reason,
+                        reply.elapsed.as_millis(),
+                        response.body.len()
+                    ),
+                    color,
+                );
+                self.response_buffer.set_text(&pretty_json(&response.body));
+            }
+            Err(e) => {
+                self.set_status(
+                    &format!("Failed after {} ms: {}", elapsed.as_millis(), e),
+                    Color::Red,
+                );
+            }
+        }
+    }
+
+    fn set_status(&mut self, text: &str, color: Color) {
+        // 错误信息里可能有 @，不要当成符号
+        self.status_frame.set_label(&text.replace('@', "@@"));
+        self.status_frame.set_label_color(color);
+        self.status_frame.redraw();
+    }
+}
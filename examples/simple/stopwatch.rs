@@ -0,0 +1,198 @@
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+// 计时器：经过的时间按 Instant 算，不靠累加 tick；tick 只负责刷新界面
+
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    // 正在计时的话，从什么时候开始的
+    started: Option<Instant>,
+    // 之前几段计时的总和
+    accumulated: Duration,
+}
+
+impl Stopwatch {
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let running = self
+            .started
+            .map(|started| now.saturating_duration_since(started))
+            .unwrap_or_default();
+        self.accumulated + running
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        self.accumulated = self.elapsed(now);
+        self.started = None;
+    }
+
+    // 归零；正在计时的话从 now 重新开始
+    pub fn reset(&mut self, now: Instant) {
+        self.accumulated = Duration::ZERO;
+        if self.started.is_some() {
+            self.started = Some(now);
+        }
+    }
+
+    // 到时间了停在 limit，不要多出一个 tick 的误差
+    pub fn stop_at(&mut self, now: Instant, limit: Duration) {
+        self.accumulated = self.elapsed(now).min(limit);
+        self.started = None;
+    }
+}
+
+// 后台线程，运行时每隔 interval 调一次 on_tick；暂停时线程阻塞，不再发消息
+pub struct Ticker {
+    control: mpsc::Sender<bool>,
+}
+
+impl Ticker {
+    // 创建时是暂停的
+    pub fn spawn(interval: Duration, on_tick: impl Fn() + Send + 'static) -> Self {
+        let (control, commands) = mpsc::channel::<bool>();
+
+        thread::spawn(move || {
+            let mut running = false;
+            loop {
+                let command = if running {
+                    commands.recv_timeout(interval)
+                } else {
+                    commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                };
+
+                match command {
+                    Ok(run) => running = run,
+                    Err(RecvTimeoutError::Timeout) => on_tick(),
+                    // Ticker 被 drop 了，线程跟着结束
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Self { control }
+    }
+
+    pub fn resume(&self) {
+        let _ = self.control.send(true);
+    }
+
+    pub fn pause(&self) {
+        let _ = self.control.send(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn new_stopwatch_is_stopped_at_zero() {
+        let t0 = Instant::now();
+        let stopwatch = Stopwatch::default();
+        assert!(!stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(t0 + secs(5)), Duration::ZERO);
+    }
+
+    #[test]
+    fn start_pause_resume() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+
+        stopwatch.start(t0);
+        assert!(stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(t0 + secs(3)), secs(3));
+
+        stopwatch.pause(t0 + secs(3));
+        assert!(!stopwatch.is_running());
+        // 暂停期间不走
+        assert_eq!(stopwatch.elapsed(t0 + secs(10)), secs(3));
+
+        stopwatch.start(t0 + secs(10));
+        assert_eq!(stopwatch.elapsed(t0 + secs(12)), secs(5));
+    }
+
+    #[test]
+    fn start_twice_keeps_the_first_start() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(t0);
+        stopwatch.start(t0 + secs(4));
+        assert_eq!(stopwatch.elapsed(t0 + secs(6)), secs(6));
+    }
+
+    #[test]
+    fn now_before_start_is_not_negative() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(t0 + secs(2));
+        assert_eq!(stopwatch.elapsed(t0), Duration::ZERO);
+    }
+
+    #[test]
+    fn reset_while_running_restarts_from_now() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(t0);
+        stopwatch.reset(t0 + secs(5));
+        assert!(stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(t0 + secs(7)), secs(2));
+    }
+
+    #[test]
+    fn reset_while_paused_stays_paused() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(t0);
+        stopwatch.pause(t0 + secs(5));
+        stopwatch.reset(t0 + secs(6));
+        assert!(!stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(t0 + secs(9)), Duration::ZERO);
+    }
+
+    #[test]
+    fn stop_at_clamps_to_the_limit() {
+        let t0 = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(t0);
+        // tick 晚到了 80ms
+        stopwatch.stop_at(t0 + Duration::from_millis(15_080), secs(15));
+        assert!(!stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(t0 + secs(60)), secs(15));
+    }
+
+    #[test]
+    fn ticker_ticks_only_while_running() {
+        let (tx, rx) = mpsc::channel();
+        let ticker = Ticker::spawn(Duration::from_millis(5), move || {
+            let _ = tx.send(());
+        });
+
+        // 创建时是暂停的
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+
+        ticker.resume();
+        assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+
+        ticker.pause();
+        // 暂停前可能还有一个在路上
+        thread::sleep(Duration::from_millis(20));
+        while rx.try_recv().is_ok() {}
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::crud::Person;
+
+// CRUD 的数据文件，JSON lines：一行一条记录
+pub const STORE_FILE_PATH: &str = "./data/crud.jsonl";
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Corrupt { line: usize, message: String },
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "{}", e),
+            StoreError::Corrupt { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+// 老版本每行只是一个 "Surname, Name" 字符串，读的时候转换成 Person
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredLine {
+    Person(Person),
+    Legacy(String),
+}
+
+// JSON lines 文件，一行一条 T；CRUD 的联系人和订票记录都用它
+pub struct Store<T> {
+    path: PathBuf,
+    records: PhantomData<T>,
+}
+
+impl<T> Store<T> {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            records: PhantomData,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // 损坏的文件改个名字留着，不要被新数据覆盖
+    pub fn quarantine(&self) -> io::Result<PathBuf> {
+        let backup = self.path.with_extension("jsonl.corrupt");
+        fs::rename(&self.path, &backup)?;
+        Ok(backup)
+    }
+
+    // 每行按 L 解析；文件不存在时返回 None
+    fn read_lines<L: DeserializeOwned>(&self) -> Result<Option<Vec<L>>, StoreError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut lines = vec![];
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = serde_json::from_str(line).map_err(|e| StoreError::Corrupt {
+                line: index + 1,
+                message: e.to_string(),
+            })?;
+            lines.push(record);
+        }
+        Ok(Some(lines))
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Store<T> {
+    // 文件不存在时返回 None，由调用方决定初始数据
+    pub fn load(&self) -> Result<Option<Vec<T>>, StoreError> {
+        self.read_lines()
+    }
+
+    // 先写临时文件，再 rename 覆盖；写到一半失败，原来的文件不受影响
+    pub fn save(&self, records: &[T]) -> Result<(), StoreError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        let mut file = File::create(&tmp_path)?;
+        for record in records {
+            let line = serde_json::to_string(record).map_err(io::Error::other)?;
+            writeln!(file, "{}", line)?;
+        }
+        file.sync_all()?;
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl Store<Person> {
+    // 和 load 一样，另外认得老版本的 "Surname, Name" 行
+    pub fn load_people(&self) -> Result<Option<Vec<Person>>, StoreError> {
+        let Some(lines) = self.read_lines::<StoredLine>()? else {
+            return Ok(None);
+        };
+
+        let mut records = vec![];
+        let mut legacy = vec![];
+        for line in lines {
+            match line {
+                StoredLine::Person(person) => records.push(person),
+                StoredLine::Legacy(text) => legacy.push(text),
+            }
+        }
+
+        // 老数据没有 id，接着最大的 id 往后编
+        let first_id = records.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+        for (id, text) in (first_id..).zip(legacy) {
+            let (surname, name) = text.split_once(',').unwrap_or((text.as_str(), ""));
+            records.push(Person {
+                id,
+                name: name.trim().to_string(),
+                surname: surname.trim().to_string(),
+            });
+        }
+
+        Ok(Some(records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        id: u32,
+        text: String,
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("store-test-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("jsonl.corrupt"));
+        path
+    }
+
+    fn note(id: u32, text: &str) -> Note {
+        Note {
+            id,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn missing_file_is_none() {
+        let store: Store<Note> = Store::new(temp_path("missing"));
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn save_then_load_any_record_type() {
+        let store = Store::new(temp_path("round-trip"));
+        let notes = vec![note(1, "first"), note(2, "second\nline")];
+        store.save(&notes).unwrap();
+
+        assert_eq!(store.load().unwrap(), Some(notes));
+        // 临时文件已经 rename 掉了
+        assert!(!store.path().with_extension("jsonl.tmp").exists());
+    }
+
+    #[test]
+    fn corrupt_line_is_reported_and_can_be_quarantined() {
+        let path = temp_path("corrupt");
+        fs::write(&path, "{\"id\":1,\"text\":\"ok\"}\n\nnot json\n").unwrap();
+        let store: Store<Note> = Store::new(&path);
+
+        match store.load() {
+            Err(StoreError::Corrupt { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a corrupt line, got {:?}", other),
+        }
+
+        let backup = store.quarantine().unwrap();
+        assert!(!path.exists());
+        assert!(backup.exists());
+        let _ = fs::remove_file(backup);
+    }
+
+    #[test]
+    fn legacy_people_get_new_ids() {
+        let path = temp_path("legacy");
+        fs::write(
+            &path,
+            "{\"id\":5,\"name\":\"Ada\",\"surname\":\"Lovelace\"}\n\"Turing, Alan\"\n\"Hopper\"\n",
+        )
+        .unwrap();
+        let people = Store::new(&path).load_people().unwrap().unwrap();
+
+        let summary: Vec<(u64, &str, &str)> = people
+            .iter()
+            .map(|p| (p.id, p.surname.as_str(), p.name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (5, "Lovelace", "Ada"),
+                (6, "Turing", "Alan"),
+                (7, "Hopper", "")
+            ]
+        );
+    }
+}
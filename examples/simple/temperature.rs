@@ -0,0 +1,203 @@
+use std::fmt;
+
+// 温度换算：都先换成 Kelvin 再换成目标单位；不依赖 fltk
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 4] = [Unit::Celsius, Unit::Fahrenheit, Unit::Kelvin, Unit::Rankine];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Celsius => "°C",
+            Unit::Fahrenheit => "°F",
+            Unit::Kelvin => "K",
+            Unit::Rankine => "°R",
+        }
+    }
+
+    pub fn to_kelvin(self, value: f64) -> f64 {
+        match self {
+            Unit::Celsius => value + 273.15,
+            Unit::Fahrenheit => (value + 459.67) * 5.0 / 9.0,
+            Unit::Kelvin => value,
+            Unit::Rankine => value * 5.0 / 9.0,
+        }
+    }
+
+    pub fn kelvin_to(self, kelvin: f64) -> f64 {
+        match self {
+            Unit::Celsius => kelvin - 273.15,
+            Unit::Fahrenheit => kelvin * 9.0 / 5.0 - 459.67,
+            Unit::Kelvin => kelvin,
+            Unit::Rankine => kelvin * 9.0 / 5.0,
+        }
+    }
+}
+
+pub fn convert(value: f64, from: Unit, to: Unit) -> f64 {
+    if from == to {
+        return value;
+    }
+    to.kelvin_to(from.to_kelvin(value))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+    NotANumber(String),
+    BelowAbsoluteZero(Unit),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::NotANumber(text) => write!(f, "\"{}\" is not a number", text),
+            TemperatureError::BelowAbsoluteZero(unit) => write!(
+                f,
+                "below absolute zero ({:.2}{})",
+                unit.kelvin_to(0.0),
+                unit.symbol()
+            ),
+        }
+    }
+}
+
+// 接受小数，小数点也可以写成逗号，例如 "36,6"
+pub fn parse_temperature(text: &str, unit: Unit) -> Result<f64, TemperatureError> {
+    let value: f64 = text
+        .trim()
+        .replace(',', ".")
+        .parse()
+        .ok()
+        .filter(|v: &f64| v.is_finite())
+        .ok_or_else(|| TemperatureError::NotANumber(text.trim().to_string()))?;
+
+    // 换算本身有浮点误差，-273.15 要算合法
+    if unit.to_kelvin(value) < -1e-9 {
+        return Err(TemperatureError::BelowAbsoluteZero(unit));
+    }
+    Ok(value)
+}
+
+// 固定的小数位数；不显示 "-0.0"
+pub fn format_temperature(value: f64, precision: usize) -> String {
+    let text = format!("{:.*}", precision, value);
+    if text.starts_with('-') && text[1..].chars().all(|c| c == '0' || c == '.') {
+        text[1..].to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn known_points() {
+        assert_close(convert(0.0, Unit::Celsius, Unit::Fahrenheit), 32.0);
+        assert_close(convert(100.0, Unit::Celsius, Unit::Fahrenheit), 212.0);
+        assert_close(convert(0.0, Unit::Celsius, Unit::Kelvin), 273.15);
+        assert_close(convert(0.0, Unit::Celsius, Unit::Rankine), 491.67);
+        assert_close(convert(-40.0, Unit::Celsius, Unit::Fahrenheit), -40.0);
+        assert_close(convert(-40.0, Unit::Fahrenheit, Unit::Celsius), -40.0);
+    }
+
+    #[test]
+    fn absolute_zero_in_every_unit() {
+        let zero = [
+            (Unit::Celsius, -273.15),
+            (Unit::Fahrenheit, -459.67),
+            (Unit::Kelvin, 0.0),
+            (Unit::Rankine, 0.0),
+        ];
+        for (unit, value) in zero {
+            assert_close(convert(0.0, Unit::Kelvin, unit), value);
+            assert_close(unit.to_kelvin(value), 0.0);
+        }
+    }
+
+    #[test]
+    fn round_trip_between_all_units() {
+        for from in Unit::ALL {
+            for to in Unit::ALL {
+                for value in [-40.0, 0.0, 36.6, 451.0, 1234.5678] {
+                    let there = convert(value, from, to);
+                    assert_close(convert(there, to, from), value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn comma_or_point_as_decimal_separator() {
+        assert_eq!(parse_temperature("36,6", Unit::Celsius), Ok(36.6));
+        assert_eq!(parse_temperature(" 36.6 ", Unit::Celsius), Ok(36.6));
+        assert_eq!(parse_temperature("-40", Unit::Fahrenheit), Ok(-40.0));
+        assert_eq!(parse_temperature("1e2", Unit::Kelvin), Ok(100.0));
+    }
+
+    #[test]
+    fn not_a_number() {
+        for text in ["", "abc", "1,2,3", "NaN", "inf", "12 °C"] {
+            assert_eq!(
+                parse_temperature(text, Unit::Celsius),
+                Err(TemperatureError::NotANumber(text.trim().to_string())),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn below_absolute_zero_is_rejected() {
+        assert_eq!(parse_temperature("-273.15", Unit::Celsius), Ok(-273.15));
+        assert_eq!(parse_temperature("-459.67", Unit::Fahrenheit), Ok(-459.67));
+        assert_eq!(parse_temperature("0", Unit::Kelvin), Ok(0.0));
+
+        assert_eq!(
+            parse_temperature("-273.16", Unit::Celsius),
+            Err(TemperatureError::BelowAbsoluteZero(Unit::Celsius))
+        );
+        assert_eq!(
+            parse_temperature("-0.001", Unit::Rankine),
+            Err(TemperatureError::BelowAbsoluteZero(Unit::Rankine))
+        );
+        assert_eq!(
+            TemperatureError::BelowAbsoluteZero(Unit::Fahrenheit).to_string(),
+            "below absolute zero (-459.67°F)"
+        );
+    }
+
+    #[test]
+    fn precision_rounding() {
+        assert_eq!(format_temperature(36.66, 1), "36.7");
+        assert_eq!(format_temperature(36.64, 1), "36.6");
+        assert_eq!(format_temperature(32.0, 0), "32");
+        assert_eq!(format_temperature(273.15, 3), "273.150");
+        assert_eq!(format_temperature(-17.7777, 2), "-17.78");
+    }
+
+    #[test]
+    fn negative_zero_is_shown_as_zero() {
+        assert_eq!(format_temperature(-0.0, 1), "0.0");
+        assert_eq!(format_temperature(-0.04, 1), "0.0");
+        assert_eq!(format_temperature(-0.4, 0), "0");
+        assert_eq!(format_temperature(-0.05, 2), "-0.05");
+    }
+}
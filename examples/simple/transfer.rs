@@ -0,0 +1,533 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::crud::Person;
+use crate::validate::{NAME_RULES, SURNAME_RULES};
+
+// CRUD 记录的导入、导出：CSV 和 JSON；不依赖 fltk
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    // 按扩展名判断，大小写不敏感
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+// 读进来的原始数据：列名 + 每行的值
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    // 每行数据在文件里的行号，报告跳过的行时用；CSV 会跳过空行，
+    // 带引号的值里也可以换行，所以不能从第一行往下数。JSON 用第几个对象
+    pub lines: Vec<usize>,
+}
+
+impl Table {
+    pub fn cell(&self, row: usize, column: Option<usize>) -> &str {
+        column
+            .and_then(|c| self.rows[row].get(c))
+            .map(|s| s.as_str())
+            .unwrap_or("")
+    }
+}
+
+// CSV 的第一行是不是列名，由用户在预览时决定；JSON 用对象的 key 做列名
+pub fn read_table(path: &Path, format: Format, has_header: bool) -> Result<Table, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match format {
+        Format::Csv => parse_csv(&text, has_header),
+        Format::Json => parse_json(&text),
+    }
+}
+
+pub fn parse_csv(text: &str, has_header: bool) -> Result<Table, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut rows = vec![];
+    let mut lines = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        lines.push(record.position().map_or(0, |p| record_line(text, p)));
+        rows.push(record.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+    }
+
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let columns = if has_header && !rows.is_empty() {
+        lines.remove(0);
+        let mut header = rows.remove(0);
+        header.resize(width, String::new());
+        header
+    } else {
+        (1..=width).map(|i| format!("Column {}", i)).collect()
+    };
+
+    Ok(Table {
+        columns,
+        rows,
+        lines,
+    })
+}
+
+// csv 给的位置是上一条记录结束的地方，前面有空行的话要再往下数
+fn record_line(text: &str, position: &csv::Position) -> usize {
+    let blank_lines = text.as_bytes()[position.byte() as usize..]
+        .iter()
+        .take_while(|b| matches!(b, b'\r' | b'\n'))
+        .filter(|b| **b == b'\n')
+        .count();
+    position.line() as usize + blank_lines
+}
+
+pub fn parse_json(text: &str) -> Result<Table, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let items = value.as_array().ok_or("expected a JSON array of objects")?;
+
+    // 所有对象的 key 合在一起做列名
+    let mut columns: Vec<String> = vec![];
+    for item in items {
+        let object = item.as_object().ok_or("expected a JSON array of objects")?;
+        for key in object.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| {
+            let object = item.as_object().cloned().unwrap_or_default();
+            columns
+                .iter()
+                .map(|c| match object.get(c) {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(Value::Null) | None => String::new(),
+                    Some(other) => other.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Table {
+        columns,
+        lines: (1..=rows.len()).collect(),
+        rows,
+    })
+}
+
+// 哪一列是 name，哪一列是 surname
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Mapping {
+    pub name: Option<usize>,
+    pub surname: Option<usize>,
+}
+
+// 按列名猜：surname / last name / family name 是姓，name / first name 是名
+pub fn guess_mapping(columns: &[String]) -> Mapping {
+    let normalized: Vec<String> = columns
+        .iter()
+        .map(|c| c.to_lowercase().replace(['_', '-', ' '], ""))
+        .collect();
+    let find = |candidates: &[&str]| {
+        normalized
+            .iter()
+            .position(|c| candidates.contains(&c.as_str()))
+    };
+
+    Mapping {
+        surname: find(&["surname", "lastname", "familyname"]),
+        name: find(&["name", "firstname", "givenname"]),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRow {
+    // 文件里的行号，见 Table::lines
+    pub row: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportPlan {
+    // (name, surname)
+    pub records: Vec<(String, String)>,
+    pub skipped: Vec<SkippedRow>,
+}
+
+impl ImportPlan {
+    pub fn report(&self) -> String {
+        let mut lines = vec![format!(
+            "Imported {} record(s), skipped {}.",
+            self.records.len(),
+            self.skipped.len()
+        )];
+        lines.extend(
+            self.skipped
+                .iter()
+                .map(|s| format!("row {}: {}", s.row, s.reason)),
+        );
+        lines.join("\n")
+    }
+}
+
+fn duplicate_key(name: &str, surname: &str) -> (String, String) {
+    (name.trim().to_lowercase(), surname.trim().to_lowercase())
+}
+
+// 空行、不符合表单校验规则的、和已有记录重复、文件内部重复的，都跳过
+pub fn plan_import(table: &Table, mapping: Mapping, existing: &[Person]) -> ImportPlan {
+    let mut seen: HashSet<(String, String)> = existing
+        .iter()
+        .map(|p| duplicate_key(&p.name, &p.surname))
+        .collect();
+
+    let mut plan = ImportPlan::default();
+    for index in 0..table.rows.len() {
+        let row = table.lines[index];
+        let name = table.cell(index, mapping.name).trim().to_string();
+        let surname = table.cell(index, mapping.surname).trim().to_string();
+
+        if name.is_empty() && surname.is_empty() {
+            plan.skipped.push(SkippedRow {
+                row,
+                reason: "empty".to_string(),
+            });
+            continue;
+        }
+
+        // 和手工输入一样的规则，否则导入的记录在表单里无法保存
+        let valid = NAME_RULES
+            .validate(&name)
+            .and_then(|_| SURNAME_RULES.validate(&surname));
+        if let Err(reason) = valid {
+            plan.skipped.push(SkippedRow { row, reason });
+            continue;
+        }
+
+        if !seen.insert(duplicate_key(&name, &surname)) {
+            plan.skipped.push(SkippedRow {
+                row,
+                reason: format!("duplicate of \"{}, {}\"", surname, name),
+            });
+            continue;
+        }
+
+        plan.records.push((name, surname));
+    }
+
+    plan
+}
+
+pub fn export(path: &Path, format: Format, people: &[Person]) -> Result<(), String> {
+    let text = match format {
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            writer
+                .write_record(["surname", "name"])
+                .map_err(|e| e.to_string())?;
+            for person in people {
+                writer
+                    .write_record([&person.surname, &person.name])
+                    .map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())?
+        }
+        Format::Json => {
+            let items: Vec<Value> = people
+                .iter()
+                .map(|p| {
+                    let mut object = Map::new();
+                    object.insert("surname".to_string(), Value::from(p.surname.as_str()));
+                    object.insert("name".to_string(), Value::from(p.name.as_str()));
+                    Value::Object(object)
+                })
+                .collect();
+            serde_json::to_string_pretty(&items).map_err(|e| e.to_string())?
+        }
+    };
+
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn person(id: u64, name: &str, surname: &str) -> Person {
+        Person {
+            id,
+            name: name.to_string(),
+            surname: surname.to_string(),
+        }
+    }
+
+    fn temp_path(name: &str, extension: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "transfer-test-{}-{}.{}",
+            name,
+            std::process::id(),
+            extension
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn table(rows: &[(&str, &str)]) -> Table {
+        Table {
+            columns: vec!["surname".to_string(), "name".to_string()],
+            rows: rows
+                .iter()
+                .map(|(surname, name)| vec![surname.to_string(), name.to_string()])
+                .collect(),
+            lines: (2..rows.len() + 2).collect(),
+        }
+    }
+
+    const MAPPING: Mapping = Mapping {
+        surname: Some(0),
+        name: Some(1),
+    };
+
+    #[test]
+    fn rows_that_fail_the_form_rules_are_skipped() {
+        let table = table(&[
+            ("Turing", "Alan"),
+            ("Lovelace", ""),
+            ("X", "Malcolm"),
+            ("Hopper", "Grace2"),
+            ("O'Neil", "Jr."),
+        ]);
+        let plan = plan_import(&table, MAPPING, &[]);
+
+        assert_eq!(
+            plan.records,
+            vec![
+                ("Alan".to_string(), "Turing".to_string()),
+                ("Jr.".to_string(), "O'Neil".to_string()),
+            ]
+        );
+        let skipped: Vec<(usize, &str)> = plan
+            .skipped
+            .iter()
+            .map(|s| (s.row, s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (3, "Name is required"),
+                (4, "Surname must be at least 2 characters"),
+                (
+                    5,
+                    "Name may only contain letters, spaces, - ' and . (found '2')"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_and_duplicate_rows_are_skipped() {
+        let existing = [Person {
+            id: 1,
+            name: "Alan".to_string(),
+            surname: "Turing".to_string(),
+        }];
+        let table = table(&[
+            ("", " "),
+            ("turing", "ALAN"),
+            ("Hopper", "Grace"),
+            ("Hopper", "Grace"),
+        ]);
+        let plan = plan_import(&table, MAPPING, &existing);
+
+        assert_eq!(
+            plan.records,
+            vec![("Grace".to_string(), "Hopper".to_string())]
+        );
+        let reasons: Vec<&str> = plan.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "empty",
+                "duplicate of \"turing, ALAN\"",
+                "duplicate of \"Hopper, Grace\""
+            ]
+        );
+        assert!(plan
+            .report()
+            .contains("row 3: duplicate of \"turing, ALAN\""));
+    }
+
+    #[test]
+    fn csv_with_header() {
+        let table = parse_csv("surname,name\nTuring,Alan\nHopper,Grace\n", true).unwrap();
+        assert_eq!(table.columns, strings(&["surname", "name"]));
+        assert_eq!(
+            table.rows,
+            vec![strings(&["Turing", "Alan"]), strings(&["Hopper", "Grace"])]
+        );
+        assert_eq!(table.lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn csv_without_header() {
+        let table = parse_csv("Turing,Alan\nHopper,Grace,extra\n", false).unwrap();
+        // 最宽的一行有几列，就有几个列名
+        assert_eq!(
+            table.columns,
+            strings(&["Column 1", "Column 2", "Column 3"])
+        );
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.cell(0, Some(2)), "");
+        assert_eq!(table.cell(1, Some(2)), "extra");
+        assert_eq!(table.lines, vec![1, 2]);
+    }
+
+    #[test]
+    fn csv_header_is_padded_to_the_widest_row() {
+        let table = parse_csv("surname\nTuring,Alan\n", true).unwrap();
+        assert_eq!(table.columns, strings(&["surname", ""]));
+        assert_eq!(parse_csv("", true).unwrap().rows.len(), 0);
+    }
+
+    #[test]
+    fn csv_quoted_fields() {
+        let text = "surname,name\n\"Smith, Jr.\",\"John \"\"Jack\"\"\"\n\"Two\nLines\",Ann\n";
+        let table = parse_csv(text, true).unwrap();
+        assert_eq!(
+            table.rows,
+            vec![
+                strings(&["Smith, Jr.", "John \"Jack\""]),
+                strings(&["Two\nLines", "Ann"])
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_rows_are_numbered_by_file_line() {
+        // 空行被跳过，带引号的值占了两行
+        let text = "surname,name\n\nTuring,Alan\n\"Two\nLines\",Ann\nX,Malcolm\n";
+        let table = parse_csv(text, true).unwrap();
+        assert_eq!(table.lines, vec![3, 4, 6]);
+
+        let plan = plan_import(&table, MAPPING, &[]);
+        assert_eq!(plan.skipped.len(), 2);
+        assert_eq!(plan.skipped[0].row, 4);
+        assert_eq!(plan.skipped[1].row, 6);
+
+        // Windows 的换行
+        let crlf = parse_csv("Turing,Alan\r\n\r\n\r\nHopper,Grace\r\n", false).unwrap();
+        assert_eq!(crlf.lines, vec![1, 4]);
+    }
+
+    #[test]
+    fn json_objects_become_rows() {
+        let text = r#"[
+            {"surname": "Turing", "name": "Alan"},
+            {"name": "Grace", "age": 85, "surname": null},
+            {}
+        ]"#;
+        let table = parse_json(text).unwrap();
+        assert_eq!(table.columns, strings(&["name", "surname", "age"]));
+        assert_eq!(
+            table.rows,
+            vec![
+                strings(&["Alan", "Turing", ""]),
+                strings(&["Grace", "", "85"]),
+                strings(&["", "", ""]),
+            ]
+        );
+        assert_eq!(table.lines, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn json_of_the_wrong_shape_is_an_error() {
+        let shape = "expected a JSON array of objects";
+        assert_eq!(parse_json(r#"{"name": "Alan"}"#), Err(shape.to_string()));
+        assert_eq!(
+            parse_json(r#"[{"name": "Alan"}, "Grace"]"#),
+            Err(shape.to_string())
+        );
+        assert_eq!(parse_json("[[1, 2]]"), Err(shape.to_string()));
+        assert!(parse_json("[{\"name\": ").is_err());
+        assert_eq!(parse_json("[]").unwrap().rows.len(), 0);
+    }
+
+    #[test]
+    fn mapping_is_guessed_from_column_names() {
+        assert_eq!(
+            guess_mapping(&strings(&["id", "First Name", "LAST_NAME"])),
+            Mapping {
+                name: Some(1),
+                surname: Some(2),
+            }
+        );
+        assert_eq!(
+            guess_mapping(&strings(&["family-name", "given_name"])),
+            Mapping {
+                name: Some(1),
+                surname: Some(0),
+            }
+        );
+        assert_eq!(
+            guess_mapping(&strings(&["Column 1", "Column 2"])),
+            Mapping::default()
+        );
+    }
+
+    #[test]
+    fn export_then_import_round_trip() {
+        let people = [
+            person(1, "Alan", "Turing"),
+            person(2, "Jr.", "O'Neil"),
+            person(3, "Zoë", "Ó Briain-Smith"),
+        ];
+
+        for (format, extension) in [(Format::Csv, "csv"), (Format::Json, "json")] {
+            let path = temp_path("round-trip", extension);
+            assert_eq!(Format::from_path(&path), Some(format));
+            export(&path, format, &people).unwrap();
+
+            let table = read_table(&path, format, true).unwrap();
+            let mapping = guess_mapping(&table.columns);
+            let plan = plan_import(&table, mapping, &[]);
+            assert!(plan.skipped.is_empty(), "{:?}", plan.skipped);
+            assert_eq!(
+                plan.records,
+                people
+                    .iter()
+                    .map(|p| (p.name.clone(), p.surname.clone()))
+                    .collect::<Vec<_>>()
+            );
+
+            // 再导入一次，全都是重复的
+            let again = plan_import(&table, mapping, &people);
+            assert!(again.records.is_empty());
+            assert_eq!(again.skipped.len(), people.len());
+            let _ = fs::remove_file(path);
+        }
+    }
+}
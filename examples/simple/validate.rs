@@ -0,0 +1,193 @@
+// CRUD 表单的校验规则；不依赖 fltk
+
+#[derive(Debug, Copy, Clone)]
+pub enum Rule {
+    // 去掉首尾空格后不能为空
+    Required,
+    MinLength(usize),
+    MaxLength(usize),
+    // 每个字符都要满足 allowed，description 用在错误提示里
+    AllowedChars {
+        description: &'static str,
+        allowed: fn(char) -> bool,
+    },
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FieldRules {
+    pub label: &'static str,
+    pub rules: &'static [Rule],
+}
+
+// 字母、空格、连字符、撇号、点，例如 "O'Neil", "Smith-Jones", "Jr."
+fn is_name_char(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, ' ' | '-' | '\'' | '.')
+}
+
+const NAME_CHARS: Rule = Rule::AllowedChars {
+    description: "letters, spaces, - ' and .",
+    allowed: is_name_char,
+};
+
+pub const NAME_RULES: FieldRules = FieldRules {
+    label: "Name",
+    rules: &[Rule::Required, Rule::MaxLength(40), NAME_CHARS],
+};
+
+pub const SURNAME_RULES: FieldRules = FieldRules {
+    label: "Surname",
+    rules: &[
+        Rule::Required,
+        Rule::MinLength(2),
+        Rule::MaxLength(40),
+        NAME_CHARS,
+    ],
+};
+
+impl Rule {
+    fn check(&self, value: &str) -> Result<(), String> {
+        let length = value.chars().count();
+        match *self {
+            Rule::Required if value.is_empty() => Err("is required".to_string()),
+            // 空值只由 Required 负责，其他规则不重复报错
+            _ if value.is_empty() => Ok(()),
+            Rule::MinLength(min) if length < min => {
+                Err(format!("must be at least {} characters", min))
+            }
+            Rule::MaxLength(max) if length > max => {
+                Err(format!("must be at most {} characters", max))
+            }
+            Rule::AllowedChars {
+                description,
+                allowed,
+            } => match value.chars().find(|c| !allowed(*c)) {
+                Some(c) => Err(format!("may only contain {} (found '{}')", description, c)),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FieldRules {
+    // 按顺序检查，返回第一条不满足的规则，例如 "Surname is required"
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        self.rules
+            .iter()
+            .try_for_each(|rule| rule.check(value))
+            .map_err(|e| format!("{} {}", self.label, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(rules: &'static [Rule]) -> FieldRules {
+        FieldRules {
+            label: "Field",
+            rules,
+        }
+    }
+
+    fn is_digit(c: char) -> bool {
+        c.is_ascii_digit()
+    }
+
+    #[test]
+    fn required() {
+        let rules = field(&[Rule::Required]);
+        assert_eq!(rules.validate(""), Err("Field is required".to_string()));
+        assert_eq!(rules.validate("x"), Ok(()));
+    }
+
+    #[test]
+    fn min_length() {
+        let rules = field(&[Rule::MinLength(3)]);
+        assert_eq!(
+            rules.validate("ab"),
+            Err("Field must be at least 3 characters".to_string())
+        );
+        assert_eq!(rules.validate("abc"), Ok(()));
+        // 空值交给 Required
+        assert_eq!(rules.validate(""), Ok(()));
+    }
+
+    #[test]
+    fn max_length() {
+        let rules = field(&[Rule::MaxLength(3)]);
+        assert_eq!(rules.validate("abc"), Ok(()));
+        assert_eq!(
+            rules.validate("abcd"),
+            Err("Field must be at most 3 characters".to_string())
+        );
+    }
+
+    #[test]
+    fn allowed_chars() {
+        let rules = field(&[Rule::AllowedChars {
+            description: "digits",
+            allowed: is_digit,
+        }]);
+        assert_eq!(rules.validate("2026"), Ok(()));
+        assert_eq!(
+            rules.validate("20x6"),
+            Err("Field may only contain digits (found 'x')".to_string())
+        );
+    }
+
+    #[test]
+    fn value_is_trimmed_first() {
+        assert_eq!(
+            NAME_RULES.validate("   "),
+            Err("Name is required".to_string())
+        );
+        // 首尾空格不算长度，也不算字符
+        assert_eq!(SURNAME_RULES.validate("  Li  "), Ok(()));
+        assert_eq!(
+            SURNAME_RULES.validate(" L "),
+            Err("Surname must be at least 2 characters".to_string())
+        );
+        assert_eq!(field(&[Rule::MaxLength(2)]).validate("\tab\n"), Ok(()));
+    }
+
+    #[test]
+    fn first_failing_rule_is_reported() {
+        // "1" 既太短又有数字，先报长度
+        assert_eq!(
+            SURNAME_RULES.validate("1"),
+            Err("Surname must be at least 2 characters".to_string())
+        );
+        assert_eq!(
+            SURNAME_RULES.validate("R2D2"),
+            Err("Surname may only contain letters, spaces, - ' and . (found '2')".to_string())
+        );
+        assert_eq!(
+            SURNAME_RULES.validate(&"9".repeat(41)),
+            Err("Surname must be at most 40 characters".to_string())
+        );
+        assert_eq!(SURNAME_RULES.validate("O'Neil-Smith Jr."), Ok(()));
+    }
+
+    #[test]
+    fn non_ascii_names_count_chars_not_bytes() {
+        assert_eq!(NAME_RULES.validate("Zoë"), Ok(()));
+        // 两个汉字是 6 个字节，但只有 2 个字符
+        assert_eq!(SURNAME_RULES.validate("欧阳"), Ok(()));
+        assert_eq!(
+            field(&[Rule::MaxLength(2)]).validate("张三丰"),
+            Err("Field must be at most 2 characters".to_string())
+        );
+        // 40 个 "é" 是 80 个字节，仍然不超长
+        assert_eq!(NAME_RULES.validate(&"é".repeat(40)), Ok(()));
+        assert_eq!(
+            NAME_RULES.validate(&"é".repeat(41)),
+            Err("Name must be at most 40 characters".to_string())
+        );
+        assert_eq!(
+            NAME_RULES.validate("Zoë☺"),
+            Err("Name may only contain letters, spaces, - ' and . (found '☺')".to_string())
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::{cell::RefCell, rc::Rc};
+
+use fltk::{
+    app,
+    enums::{Color, Key, Shortcut},
+    group::{Group, Pack, PackType},
+    output::Output,
+    prelude::*,
+    widget_extends,
+};
+
+use crate::widgets::StyledButton;
+
+// 计算器：上面显示，下面 5 行按键；是个 Group，可以放进任何窗口
+//
+//   let calc = Calculator::new(20, 20, 360, 460);
+//   calc.focus();
+
+// 每行按键的高度；剩下的高度都给显示区
+const ROW_HEIGHT: i32 = 60;
+const ROWS: i32 = 5;
+const GAP: i32 = 20;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ops {
+    None,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    CE,
+    C,
+    Back,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Keypress {
+    Number(i32),
+    Op(Ops),
+    Dot,
+}
+
+// 显示的内容、正在输入的数、上一个数和等着算的运算
+#[derive(Debug, Clone)]
+pub struct CalcState {
+    display: String,
+    txt: String,
+    old_val: String,
+    operation: Ops,
+}
+
+impl Default for CalcState {
+    fn default() -> Self {
+        Self {
+            display: String::from("0"),
+            txt: String::from("0"),
+            old_val: String::from("0"),
+            operation: Ops::None,
+        }
+    }
+}
+
+impl CalcState {
+    pub fn display(&self) -> &str {
+        &self.display
+    }
+
+    pub fn press(&mut self, key: Keypress) {
+        match key {
+            Keypress::Number(num) => {
+                if self.display == "0" {
+                    self.txt.clear();
+                }
+                self.txt.push_str(&num.to_string());
+                self.display = self.txt.clone();
+            }
+            Keypress::Dot => {
+                if self.operation == Ops::Eq {
+                    self.txt.clear();
+                    self.operation = Ops::None;
+                    self.display = String::from("0.");
+                    self.txt.push_str("0.");
+                }
+                if !self.txt.contains('.') {
+                    self.txt.push('.');
+                    self.display = self.txt.clone();
+                }
+            }
+            Keypress::Op(op) => match op {
+                Ops::Add | Ops::Sub | Ops::Div | Ops::Mul => {
+                    self.old_val = self.display.clone();
+                    self.operation = op;
+                    self.display = String::from("0");
+                }
+                Ops::Back => {
+                    self.txt.pop();
+                    if self.display.len() > 1 {
+                        self.display = self.txt.clone();
+                    } else {
+                        self.display = String::from("0");
+                    }
+                }
+                Ops::CE => {
+                    self.txt = String::from("0");
+                    self.old_val.clear();
+                    self.display = self.txt.clone();
+                }
+                Ops::C => {
+                    self.txt = String::from("0");
+                    self.display = self.txt.clone();
+                }
+                Ops::Eq => {
+                    // CE 之后 old_val 是空的，当成 0
+                    let old: f64 = self.old_val.parse().unwrap_or(0.0);
+                    let new: f64 = self.display.parse().unwrap_or(0.0);
+                    let val = match self.operation {
+                        Ops::Div => old / new,
+                        Ops::Mul => old * new,
+                        Ops::Add => old + new,
+                        Ops::Sub => old - new,
+                        _ => new,
+                    };
+                    self.operation = Ops::None;
+                    self.txt = String::from("0");
+                    self.display = val.to_string();
+                }
+                Ops::None => (),
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Calculator {
+    inner: Group,
+}
+
+impl Calculator {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        let inner = Group::new(x, y, w, h, None);
+        let key_width = w / 4;
+        let keys_y = y + h - ROW_HEIGHT * ROWS;
+
+        // 上面的顯示區域
+        let mut out = Output::new(x, y, w, keys_y - y - GAP, None);
+        out.set_text_size(36);
+        out.set_value("0");
+
+        let state = Rc::new(RefCell::new(CalcState::default()));
+
+        // 下面的按键区域：5 行
+        let vpack = Pack::new(x, keys_y, w, ROW_HEIGHT * ROWS, None);
+        for row in [
+            &["CE", "C", "@<-", "/"][..],
+            &["7", "8", "9", "x"],
+            &["4", "5", "6", "-"],
+            &["1", "2", "3", "+"],
+            &[".", "0", "="],
+        ] {
+            let mut hpack = Pack::new(0, 0, w, ROW_HEIGHT, None);
+            for title in row {
+                let mut key = make_key(title, key_width);
+                let press = keypress(title);
+                key.set_callback({
+                    let state = state.clone();
+                    let mut out = out.clone();
+                    move |_| {
+                        let mut state = state.borrow_mut();
+                        state.press(press);
+                        out.set_value(state.display());
+                    }
+                });
+            }
+            hpack.end();
+            hpack.set_type(PackType::Horizontal);
+        }
+        vpack.end();
+        inner.end();
+
+        Self { inner }
+    }
+
+    // 焦点放在计算器上，按键都当成快捷键；不能放在某个键上，
+    // 不然回车会按下那个键，而不是 "="
+    pub fn focus(&self) {
+        app::set_focus(&self.inner);
+    }
+}
+
+fn keypress(title: &str) -> Keypress {
+    match title {
+        "+" => Keypress::Op(Ops::Add),
+        "-" => Keypress::Op(Ops::Sub),
+        "x" => Keypress::Op(Ops::Mul),
+        "/" => Keypress::Op(Ops::Div),
+        "=" => Keypress::Op(Ops::Eq),
+        "CE" => Keypress::Op(Ops::CE),
+        "C" => Keypress::Op(Ops::C),
+        "@<-" => Keypress::Op(Ops::Back),
+        "." => Keypress::Dot,
+        _ => title
+            .parse()
+            .map(Keypress::Number)
+            .unwrap_or(Keypress::Op(Ops::None)),
+    }
+}
+
+// 按键的大小、颜色和快捷键
+fn make_key(title: &str, width: i32) -> StyledButton {
+    let mut b = StyledButton::pill(width, 0, title);
+    b.set_label_size(20);
+    // 用键盘就够了，点击时不抢焦点
+    b.visible_focus(false);
+    match title {
+        "0" => {
+            b.resize(0, 0, width * 2, 0);
+            b.set_color(Color::Light3);
+            b.set_shortcut(Shortcut::None | '0');
+        }
+        "CE" => {
+            b.set_color(Color::Red);
+            b.set_shortcut(Shortcut::None | Key::Delete);
+        }
+        "x" | "/" | "+" | "-" | "=" | "C" | "@<-" => {
+            b.set_color(Color::Yellow);
+            let shortcut = if title == "x" {
+                '*'
+            } else {
+                title.chars().next().unwrap()
+            };
+            b.set_shortcut(Shortcut::None | shortcut);
+            if shortcut == '@' {
+                b.set_shortcut(Shortcut::None | Key::BackSpace);
+            }
+            if shortcut == '=' {
+                b.set_shortcut(Shortcut::None | Key::Enter);
+            }
+        }
+        _ => {
+            b.set_color(Color::Light3);
+            b.set_shortcut(Shortcut::None | title.chars().next().unwrap());
+        }
+    }
+    b
+}
+
+widget_extends!(Calculator, Group, inner);
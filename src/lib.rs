@@ -0,0 +1,7 @@
+// 各个 example 共用的代码
+
+pub mod calculator;
+pub mod pricing;
+pub mod settings;
+pub mod theme;
+pub mod widgets;
@@ -7,12 +7,7 @@ use fltk::{
     prelude::*,
     window::Window,
 };
-
-fn style_button(btn: &mut button::Button) {
-    btn.set_color(Color::Cyan);
-    btn.set_frame(FrameType::RFlatBox);
-    btn.clear_visible_focus();
-}
+use hello_fltk::{theme, widgets::StyledButton};
 
 fn show_dialog() -> MyDialog {
     MyDialog::default()
@@ -39,8 +34,8 @@ impl MyDialog {
             .with_label("Enter name:");
         let mut inp = input::Input::default().with_size(100, 0);
         inp.set_frame(FrameType::FlatBox);
-        let mut ok = button::Button::default().with_size(80, 0).with_label("Ok");
-        style_button(&mut ok);
+        let mut ok = StyledButton::pill(80, 0, "Ok");
+        ok.set_color(Color::Cyan);
         pack.end();
         win.end();
         win.make_modal(true);
@@ -69,6 +64,7 @@ fn main() {
     println!("Hello, world!");
 
     let a = app::App::default();
+    theme::apply_saved();
 
     let mut wind = Window::new(100, 100, 400, 300, "My Window");
     let mut frame = Frame::new(20, 20, 200, 40, "Will be replaced");
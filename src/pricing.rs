@@ -0,0 +1,669 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+// 手工模板计价接口，和 fetch.http 里的请求一样：
+//   POST {base}/bcm/api/public/pricing/manualTemplatePricing
+// simple 的 Pricing 面板和 pricing_mock 共用这里的路径和编码
+//
+// 为什么不用 ureq / reqwest：接口只在内网用 http，一次一个 POST，回来的是一段 JSON；
+// 为这个引入 HTTP 客户端要多编译 TLS、异步运行时之类的一大串依赖。
+// 自己发只需要处理 Content-Length、chunked 和读到对方关闭，都在下面，有测试。
+// 不支持的：https、重定向、keep-alive、代理；要用这些时再换成正经的客户端
+
+pub const PRICING_PATH: &str = "/bcm/api/public/pricing/manualTemplatePricing";
+pub const DEFAULT_BASE_URL: &str = "http://127.0.0.1:8002";
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingRequest {
+    pub so_head_uuid_list: Vec<u64>,
+    pub bcm_tmpl_id_list: Vec<u64>,
+    pub company_code: String,
+    pub operator: String,
+}
+
+#[derive(Debug)]
+pub enum PricingError {
+    // 第几行 (从 1 开始)，原文
+    InvalidId {
+        field: &'static str,
+        line: usize,
+        text: String,
+    },
+    EmptyField(&'static str),
+    InvalidUrl(String),
+    Io(io::Error),
+    InvalidResponse(String),
+}
+
+impl fmt::Display for PricingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PricingError::InvalidId { field, line, text } => {
+                write!(f, "{} line {}: \"{}\" is not a number", field, line, text)
+            }
+            PricingError::EmptyField(field) => write!(f, "{} is empty", field),
+            PricingError::InvalidUrl(url) => {
+                write!(f, "\"{}\" is not a valid http:// base URL", url)
+            }
+            PricingError::Io(e) => write!(f, "{}", e),
+            PricingError::InvalidResponse(reason) => write!(f, "bad response: {}", reason),
+        }
+    }
+}
+
+impl From<io::Error> for PricingError {
+    fn from(e: io::Error) -> Self {
+        PricingError::Io(e)
+    }
+}
+
+// 一列编号，通常是从表格里复制的；换行、空格、逗号都可以分隔，JSON 数组的括号也忽略
+pub fn parse_id_list(field: &'static str, text: &str) -> Result<Vec<u64>, PricingError> {
+    let mut ids = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let words = line
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '[' | ']' | '"'))
+            .filter(|w| !w.is_empty());
+        for word in words {
+            let id = word.parse().map_err(|_| PricingError::InvalidId {
+                field,
+                line: i + 1,
+                text: word.to_string(),
+            })?;
+            ids.push(id);
+        }
+    }
+    if ids.is_empty() {
+        return Err(PricingError::EmptyField(field));
+    }
+    Ok(ids)
+}
+
+pub fn build_request(
+    so_head_uuids: &str,
+    template_ids: &str,
+    company_code: &str,
+    operator: &str,
+) -> Result<PricingRequest, PricingError> {
+    let company_code = company_code.trim();
+    if company_code.is_empty() {
+        return Err(PricingError::EmptyField("Company code"));
+    }
+    let operator = operator.trim();
+    if operator.is_empty() {
+        return Err(PricingError::EmptyField("Operator"));
+    }
+
+    Ok(PricingRequest {
+        so_head_uuid_list: parse_id_list("SO head UUIDs", so_head_uuids)?,
+        bcm_tmpl_id_list: parse_id_list("Template IDs", template_ids)?,
+        company_code: company_code.to_string(),
+        operator: operator.to_string(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    // IPv6 地址不带方括号，例如 ::1
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl Endpoint {
+    // Host 头里的写法：IPv6 要加方括号，80 端口省略
+    pub fn authority(&self) -> String {
+        let host = if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.clone()
+        };
+        if self.port == 80 {
+            host
+        } else {
+            format!("{}:{}", host, self.port)
+        }
+    }
+}
+
+// base URL 可以带前缀路径，例如 http://gateway:8080/pricing-test；
+// IPv6 写成 http://[::1]:8002
+pub fn endpoint(base_url: &str) -> Result<Endpoint, PricingError> {
+    let invalid = || PricingError::InvalidUrl(base_url.to_string());
+
+    let rest = base_url
+        .trim()
+        .strip_prefix("http://")
+        .ok_or_else(invalid)?;
+    let (authority, prefix) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+
+    // 先把 host 拆出来，剩下的是空的或者 ":端口"
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, port) = bracketed.split_once(']').ok_or_else(invalid)?;
+            if !host.contains(':') {
+                return Err(invalid());
+            }
+            (host, port)
+        }
+        None => match authority.find(':') {
+            Some(i) => authority.split_at(i),
+            None => (authority, ""),
+        },
+    };
+    let port = match port {
+        "" => 80,
+        port => port
+            .strip_prefix(':')
+            .and_then(|port| port.parse().ok())
+            .ok_or_else(invalid)?,
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(Endpoint {
+        host: host.to_string(),
+        port,
+        path: format!("{}{}", prefix.trim_end_matches('/'), PRICING_PATH),
+    })
+}
+
+// 每次一个连接，Connection: close，读到对方关闭为止
+pub fn encode_request(endpoint: &Endpoint, body: &str) -> String {
+    format!(
+        "POST {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Content-Type: application/json\r\n\
+         Accept: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {}",
+        endpoint.path,
+        endpoint.authority(),
+        body.len(),
+        body
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub fn parse_response(bytes: &[u8]) -> Result<HttpResponse, PricingError> {
+    let invalid = |reason: &str| PricingError::InvalidResponse(reason.to_string());
+
+    let head_end = bytes
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| invalid("no end of headers"))?;
+    let head = String::from_utf8_lossy(&bytes[..head_end]);
+    let raw_body = &bytes[head_end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let mut parts = status_line.splitn(3, ' ');
+    if !parts.next().unwrap_or_default().starts_with("HTTP/") {
+        return Err(invalid("not an HTTP response"));
+    }
+    let status = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("no status code"))?;
+    let reason = parts.next().unwrap_or_default().to_string();
+
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let mut response = HttpResponse {
+        status,
+        reason,
+        headers,
+        body: String::new(),
+    };
+
+    let body = if response
+        .header("Transfer-Encoding")
+        .is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+    {
+        decode_chunked(raw_body).ok_or_else(|| invalid("broken chunked body"))?
+    } else if let Some(length) = response.header("Content-Length") {
+        let length: usize = length.parse().map_err(|_| invalid("bad Content-Length"))?;
+        if raw_body.len() < length {
+            return Err(invalid("body shorter than Content-Length"));
+        }
+        raw_body[..length].to_vec()
+    } else {
+        raw_body.to_vec()
+    };
+    response.body = String::from_utf8_lossy(&body).into_owned();
+    Ok(response)
+}
+
+fn decode_chunked(mut bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let line_end = bytes.windows(2).position(|w| w == b"\r\n")?;
+        let size_line = std::str::from_utf8(&bytes[..line_end]).ok()?;
+        // 后面可能跟着 ;name=value 扩展
+        let size_text = size_line.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_text, 16).ok()?;
+        bytes = &bytes[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(bytes.get(..size)?);
+        bytes = bytes.get(size + 2..)?;
+    }
+}
+
+// 是 JSON 就缩进排好，不是就原样显示
+pub fn pretty_json(text: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(text)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| text.to_string())
+}
+
+#[derive(Debug)]
+pub struct PricingReply {
+    pub response: HttpResponse,
+    pub elapsed: Duration,
+}
+
+// 会阻塞，放在后台线程里调
+pub fn send(base_url: &str, request: &PricingRequest) -> Result<PricingReply, PricingError> {
+    let endpoint = endpoint(base_url)?;
+    let body = serde_json::to_string(request).map_err(io::Error::other)?;
+    let started = Instant::now();
+
+    let address = (endpoint.host.as_str(), endpoint.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| PricingError::InvalidUrl(base_url.to_string()))?;
+    let mut stream = TcpStream::connect_timeout(&address, REQUEST_TIMEOUT)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    stream.write_all(encode_request(&endpoint, &body).as_bytes())?;
+    let mut bytes = vec![];
+    stream.read_to_end(&mut bytes)?;
+
+    Ok(PricingReply {
+        response: parse_response(&bytes)?,
+        elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn response(text: &str) -> Result<HttpResponse, PricingError> {
+        parse_response(text.as_bytes())
+    }
+
+    fn invalid_response(result: Result<HttpResponse, PricingError>) -> String {
+        match result {
+            Err(PricingError::InvalidResponse(reason)) => reason,
+            other => panic!("expected an invalid response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ids_split_on_any_separator() {
+        let text = "1001\n1002, 1003;1004\r\n\n[\"1005\"]\t1006";
+        assert_eq!(
+            parse_id_list("SO head UUIDs", text).unwrap(),
+            vec![1001, 1002, 1003, 1004, 1005, 1006]
+        );
+    }
+
+    #[test]
+    fn bad_id_reports_line_and_text() {
+        match parse_id_list("SO head UUIDs", "1001\n\n1002 abc\n") {
+            Err(PricingError::InvalidId { field, line, text }) => {
+                assert_eq!((field, line, text.as_str()), ("SO head UUIDs", 3, "abc"))
+            }
+            other => panic!("expected an invalid id, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_id_list("Template IDs", "-1"),
+            Err(PricingError::InvalidId { .. })
+        ));
+    }
+
+    #[test]
+    fn empty_id_list_is_an_error() {
+        assert!(matches!(
+            parse_id_list("Template IDs", " ,\n[]\n"),
+            Err(PricingError::EmptyField("Template IDs"))
+        ));
+    }
+
+    #[test]
+    fn build_request_trims_and_serializes_camel_case() {
+        let request = build_request("1\n2", "7", " C001 ", " alice ").unwrap();
+        assert_eq!(
+            request,
+            PricingRequest {
+                so_head_uuid_list: vec![1, 2],
+                bcm_tmpl_id_list: vec![7],
+                company_code: "C001".to_string(),
+                operator: "alice".to_string(),
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"soHeadUuidList":[1,2],"bcmTmplIdList":[7],"companyCode":"C001","operator":"alice"}"#
+        );
+    }
+
+    #[test]
+    fn build_request_checks_every_field() {
+        assert!(matches!(
+            build_request("1", "7", " ", "alice"),
+            Err(PricingError::EmptyField("Company code"))
+        ));
+        assert!(matches!(
+            build_request("1", "7", "C001", ""),
+            Err(PricingError::EmptyField("Operator"))
+        ));
+        assert!(matches!(
+            build_request("", "7", "C001", "alice"),
+            Err(PricingError::EmptyField("SO head UUIDs"))
+        ));
+        assert!(matches!(
+            build_request("1", "x", "C001", "alice"),
+            Err(PricingError::InvalidId {
+                field: "Template IDs",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn endpoint_without_port_uses_80() {
+        let endpoint = endpoint("http://pricing.local").unwrap();
+        assert_eq!(endpoint.host, "pricing.local");
+        assert_eq!(endpoint.port, 80);
+        assert_eq!(endpoint.path, PRICING_PATH);
+        assert_eq!(endpoint.authority(), "pricing.local");
+    }
+
+    #[test]
+    fn endpoint_keeps_prefix_path() {
+        let endpoint = endpoint(" http://gateway:8080/pricing-test/ ").unwrap();
+        assert_eq!(endpoint.host, "gateway");
+        assert_eq!(endpoint.port, 8080);
+        assert_eq!(endpoint.path, format!("/pricing-test{}", PRICING_PATH));
+        assert_eq!(endpoint.authority(), "gateway:8080");
+    }
+
+    #[test]
+    fn endpoint_accepts_ipv6() {
+        let endpoint = endpoint("http://[::1]:8002").unwrap();
+        assert_eq!(endpoint.host, "::1");
+        assert_eq!(endpoint.port, 8002);
+        assert_eq!(endpoint.authority(), "[::1]:8002");
+
+        let endpoint = super::endpoint("http://[fe80::1]/api").unwrap();
+        assert_eq!(endpoint.host, "fe80::1");
+        assert_eq!(endpoint.port, 80);
+        assert_eq!(endpoint.path, format!("/api{}", PRICING_PATH));
+        assert_eq!(endpoint.authority(), "[fe80::1]");
+    }
+
+    #[test]
+    fn endpoint_rejects_bad_urls() {
+        for url in [
+            "https://pricing.local",
+            "pricing.local:8002",
+            "http://",
+            "http://:8002",
+            "http://host:",
+            "http://host:port",
+            "http://host:70000",
+            "http://host:80:90",
+            "http://[::1",
+            "http://[::1]8002",
+            "http://[host]:8002",
+        ] {
+            assert!(
+                matches!(endpoint(url), Err(PricingError::InvalidUrl(_))),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn request_has_headers_and_body() {
+        let endpoint = endpoint("http://127.0.0.1:8002").unwrap();
+        let request = encode_request(&endpoint, "{\"a\":1}");
+        assert_eq!(
+            request,
+            format!(
+                "POST {} HTTP/1.1\r\n\
+                 Host: 127.0.0.1:8002\r\n\
+                 Content-Type: application/json\r\n\
+                 Accept: application/json\r\n\
+                 Content-Length: 7\r\n\
+                 Connection: close\r\n\
+                 \r\n\
+                 {{\"a\":1}}",
+                PRICING_PATH
+            )
+        );
+    }
+
+    #[test]
+    fn response_with_content_length() {
+        let response =
+            response("HTTP/1.1 200 OK\r\ncontent-length: 5\r\nX-A:  b \r\n\r\nhello, extra")
+                .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.reason, "OK");
+        assert_eq!(response.header("Content-Length"), Some("5"));
+        assert_eq!(response.header("x-a"), Some("b"));
+        assert_eq!(response.body, "hello");
+    }
+
+    #[test]
+    fn response_reason_may_have_spaces_or_be_missing() {
+        let response = response("HTTP/1.1 404 Not Found\r\n\r\n").unwrap();
+        assert_eq!(
+            (response.status, response.reason.as_str()),
+            (404, "Not Found")
+        );
+        let response = super::parse_response(b"HTTP/1.1 204\r\n\r\n").unwrap();
+        assert_eq!((response.status, response.reason.as_str()), (204, ""));
+    }
+
+    #[test]
+    fn response_without_length_reads_to_the_end() {
+        let response = response("HTTP/1.0 200 OK\r\n\r\n{\"code\":200}").unwrap();
+        assert_eq!(response.body, "{\"code\":200}");
+    }
+
+    #[test]
+    fn body_shorter_than_content_length() {
+        let reason = invalid_response(response(
+            "HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort",
+        ));
+        assert_eq!(reason, "body shorter than Content-Length");
+        let reason = invalid_response(response(
+            "HTTP/1.1 200 OK\r\nContent-Length: ten\r\n\r\nshort",
+        ));
+        assert_eq!(reason, "bad Content-Length");
+    }
+
+    #[test]
+    fn headers_must_end_with_a_blank_line() {
+        let reason = invalid_response(response("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nok"));
+        assert_eq!(reason, "no end of headers");
+        assert_eq!(invalid_response(response("")), "no end of headers");
+    }
+
+    #[test]
+    fn not_http() {
+        assert_eq!(
+            invalid_response(response("SSH-2.0-OpenSSH\r\n\r\n")),
+            "not an HTTP response"
+        );
+        assert_eq!(
+            invalid_response(response("HTTP/1.1 OK\r\n\r\n")),
+            "no status code"
+        );
+    }
+
+    #[test]
+    fn chunked_body_with_extensions() {
+        let response = response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: Chunked\r\n\r\n\
+             5;name=value\r\nhello\r\n\
+             7 ; x=\"y\"\r\n, world\r\n\
+             0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(response.body, "hello, world");
+    }
+
+    #[test]
+    fn chunked_body_sizes_are_hex() {
+        let body = "a".repeat(0x1a);
+        let text = format!(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n1A\r\n{}\r\n0\r\n\r\n",
+            body
+        );
+        assert_eq!(response(&text).unwrap().body, body);
+    }
+
+    #[test]
+    fn broken_chunked_body() {
+        for body in [
+            "5\r\nhel",
+            "5\r\nhello",
+            "zz\r\nhello\r\n0\r\n\r\n",
+            "5\r\nhello\r\n",
+        ] {
+            let text = format!(
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{}",
+                body
+            );
+            assert_eq!(
+                invalid_response(response(&text)),
+                "broken chunked body",
+                "{:?}",
+                body
+            );
+        }
+    }
+
+    #[test]
+    fn pretty_json_only_touches_json() {
+        assert_eq!(pretty_json("{\"a\":[1]}"), "{\n  \"a\": [\n    1\n  ]\n}");
+        assert_eq!(pretty_json("<html>502</html>"), "<html>502</html>");
+    }
+
+    #[test]
+    fn send_to_a_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/prefix", listener.local_addr().unwrap());
+
+        // 收一个请求，把请求行、Host 和 body 交回来，用 chunked 回复
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut host = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                let (name, value) = line.split_once(':').unwrap();
+                match name.to_lowercase().as_str() {
+                    "host" => host = value.trim().to_string(),
+                    "content-length" => length = value.trim().parse().unwrap(),
+                    _ => (),
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut stream = stream;
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                      c\r\n{\"code\":200}\r\n0\r\n\r\n",
+                )
+                .unwrap();
+            (request_line, host, String::from_utf8(body).unwrap())
+        });
+
+        let request = build_request("1001\n1002", "7", "C001", "alice").unwrap();
+        let reply = send(&base_url, &request).unwrap();
+        assert_eq!(reply.response.status, 200);
+        assert_eq!(reply.response.body, "{\"code\":200}");
+
+        let (request_line, host, body) = server.join().unwrap();
+        assert_eq!(
+            request_line,
+            format!("POST /prefix{} HTTP/1.1\r\n", PRICING_PATH)
+        );
+        assert_eq!(
+            host,
+            base_url["http://".len()..base_url.len() - "/prefix".len()]
+        );
+        assert_eq!(
+            serde_json::from_str::<PricingRequest>(&body).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn send_reports_connection_errors() {
+        // 先占一个端口再放掉，这个端口上没有人在听
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let request = build_request("1", "7", "C001", "alice").unwrap();
+        assert!(matches!(
+            send(&format!("http://{}", address), &request),
+            Err(PricingError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::theme::{SchemeName, Theme};
+
+// 下次启动还要用的选择，存成一个小 JSON；读不到就用默认值
+
+pub const SETTINGS_FILE_PATH: &str = "./data/settings.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub theme: Option<String>,
+    #[serde(default)]
+    pub scheme: Option<SchemeName>,
+    // 启动器上次打开的是哪个工具
+    #[serde(default)]
+    pub tool: Option<String>,
+    // 计价面板上次用的 base URL
+    #[serde(default)]
+    pub pricing_url: Option<String>,
+}
+
+impl Settings {
+    // 文件不存在或者坏了都当成没有设置过
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    // 选过的主题在列表里的位置；没选过或者已经删掉了就用第一个
+    pub fn theme_index(&self, themes: &[Theme]) -> usize {
+        self.theme
+            .as_ref()
+            .and_then(|name| themes.iter().position(|t| t.name == *name))
+            .unwrap_or(0)
+    }
+
+    // 单独选过 scheme 就用选的，否则用主题自带的
+    pub fn scheme_for(&self, theme: &Theme) -> SchemeName {
+        self.scheme.unwrap_or(theme.scheme)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let path = Path::new(path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let text = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, text)?;
+        fs::rename(&tmp_path, path)
+    }
+}
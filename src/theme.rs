@@ -0,0 +1,585 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::sync::OnceLock;
+
+use fltk::{
+    app::{self, Scheme},
+    enums::{Color, Font},
+    group::Group,
+    input::Input,
+    menu::Choice,
+    prelude::*,
+    widget::Widget,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{Settings, SETTINGS_FILE_PATH};
+
+// 主题：scheme 之外再加一套调色板和字体，切换时所有打开的窗口一起变
+//
+// 自定义主题写在 TOML 里，一个 [[theme]] 一个主题，同名的会覆盖自带的：
+//   [[theme]]
+//   name = "Solarized"
+//   scheme = "gtk"
+//   background = "#fdf6e3"
+//   foreground = "#657b83"
+//   selection = "#268bd2"
+//   accent = "#cb4b16"
+//   font = "courier"
+//   font_size = 14
+
+pub const THEMES_FILE_PATH: &str = "./assets/themes.toml";
+
+const DEFAULT_FONT_SIZE: i32 = 14;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemeName {
+    #[default]
+    Base,
+    Plastic,
+    Gtk,
+    Gleam,
+    Oxy,
+}
+
+impl SchemeName {
+    pub const ALL: [SchemeName; 5] = [
+        SchemeName::Base,
+        SchemeName::Plastic,
+        SchemeName::Gtk,
+        SchemeName::Gleam,
+        SchemeName::Oxy,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SchemeName::Base => "Base",
+            SchemeName::Plastic => "Plastic",
+            SchemeName::Gtk => "Gtk",
+            SchemeName::Gleam => "Gleam",
+            SchemeName::Oxy => "Oxy",
+        }
+    }
+
+    pub fn scheme(self) -> Scheme {
+        match self {
+            SchemeName::Base => Scheme::Base,
+            SchemeName::Plastic => Scheme::Plastic,
+            SchemeName::Gtk => Scheme::Gtk,
+            SchemeName::Gleam => Scheme::Gleam,
+            SchemeName::Oxy => Scheme::Oxy,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FontFace {
+    #[default]
+    Helvetica,
+    Courier,
+    Times,
+    Screen,
+}
+
+impl FontFace {
+    pub fn font(self) -> Font {
+        match self {
+            FontFace::Helvetica => Font::Helvetica,
+            FontFace::Courier => Font::Courier,
+            FontFace::Times => Font::Times,
+            FontFace::Screen => Font::Screen,
+        }
+    }
+}
+
+// TOML 里写成 "#rrggbb"
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let hex = text
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| format!("`{}` is not a #rrggbb color", text))?;
+        let part = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| format!("`{}` is not a #rrggbb color", text))
+        };
+        Ok(Rgb(part(0)?, part(2)?, part(4)?))
+    }
+}
+
+impl Rgb {
+    pub fn color(self) -> Color {
+        Color::from_rgb(self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    #[serde(default)]
+    pub scheme: SchemeName,
+    // 窗口、按钮的底色
+    pub background: Rgb,
+    // 输入框、列表的底色，不写就和 background 一样
+    #[serde(default)]
+    pub field: Option<Rgb>,
+    pub foreground: Rgb,
+    pub selection: Rgb,
+    // 进度条之类需要突出的地方
+    pub accent: Rgb,
+    #[serde(default)]
+    pub font: FontFace,
+    #[serde(default = "default_font_size")]
+    pub font_size: i32,
+}
+
+fn default_font_size() -> i32 {
+    DEFAULT_FONT_SIZE
+}
+
+pub fn builtin_themes() -> Vec<Theme> {
+    vec![
+        Theme {
+            name: "Light".to_string(),
+            scheme: SchemeName::Base,
+            background: Rgb(0xc0, 0xc0, 0xc0),
+            field: Some(Rgb(0xff, 0xff, 0xff)),
+            foreground: Rgb(0x00, 0x00, 0x00),
+            selection: Rgb(0x00, 0x00, 0x80),
+            accent: Rgb(0x00, 0x00, 0xff),
+            font: FontFace::Helvetica,
+            font_size: DEFAULT_FONT_SIZE,
+        },
+        Theme {
+            name: "Dark".to_string(),
+            scheme: SchemeName::Gtk,
+            background: Rgb(0x33, 0x33, 0x33),
+            field: Some(Rgb(0x1e, 0x1e, 0x1e)),
+            foreground: Rgb(0xe6, 0xe6, 0xe6),
+            selection: Rgb(0x3d, 0x6e, 0xb4),
+            accent: Rgb(0xff, 0xa0, 0x00),
+            font: FontFace::Helvetica,
+            font_size: DEFAULT_FONT_SIZE,
+        },
+        Theme {
+            name: "High contrast".to_string(),
+            scheme: SchemeName::Base,
+            background: Rgb(0x00, 0x00, 0x00),
+            field: Some(Rgb(0x00, 0x00, 0x00)),
+            foreground: Rgb(0xff, 0xff, 0xff),
+            selection: Rgb(0xff, 0xff, 0x00),
+            accent: Rgb(0x00, 0xff, 0xff),
+            font: FontFace::Helvetica,
+            font_size: DEFAULT_FONT_SIZE + 4,
+        },
+    ]
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "{}", e),
+            ThemeError::Parse(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ThemesFile {
+    #[serde(default)]
+    theme: Vec<Theme>,
+}
+
+pub fn parse_themes(text: &str) -> Result<Vec<Theme>, ThemeError> {
+    let file: ThemesFile = toml::from_str(text).map_err(|e| ThemeError::Parse(e.to_string()))?;
+    Ok(file.theme)
+}
+
+// 自带的主题加上文件里的；文件不存在不算错
+pub fn load_themes(path: &str) -> Result<Vec<Theme>, ThemeError> {
+    let custom = match fs::read_to_string(path) {
+        Ok(text) => parse_themes(&text)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(ThemeError::Io(e)),
+    };
+    Ok(merge_themes(builtin_themes(), custom))
+}
+
+// 同名的主题，后面的覆盖前面的，位置不变
+pub fn merge_themes(mut themes: Vec<Theme>, custom: Vec<Theme>) -> Vec<Theme> {
+    for theme in custom {
+        match themes.iter_mut().find(|t| t.name == theme.name) {
+            Some(existing) => *existing = theme,
+            None => themes.push(theme),
+        }
+    }
+    themes
+}
+
+// 换主题：全局的颜色、字体，再把已经打开的窗口里的字号按比例调整
+pub fn apply(theme: &Theme, scheme: SchemeName) {
+    let old_size = app::font_size();
+
+    app::set_scheme(scheme.scheme());
+
+    let Rgb(r, g, b) = theme.background;
+    app::background(r, g, b);
+    let Rgb(r, g, b) = theme.field.unwrap_or(theme.background);
+    app::background2(r, g, b);
+    let Rgb(r, g, b) = theme.foreground;
+    app::foreground(r, g, b);
+    let Rgb(r, g, b) = theme.selection;
+    app::set_selection_color(r, g, b);
+
+    // 替换 0 号字体的 face，用默认字体的 label 都会跟着变；
+    // 不用 app::set_font，它会把原来的 face 换到别的编号上，来回切几次就乱了
+    Font::set_font(Font::Helvetica, &face_name(theme.font));
+    app::set_font_size(theme.font_size);
+
+    // 颜色是索引色，会自动跟着变；字号是每个 widget 自己存的，要一个个改
+    forget_deleted_widgets();
+    if let Some(windows) = app::windows() {
+        for mut window in windows {
+            if let Some(group) = window.as_group() {
+                resize_fonts(&group, old_size, theme.font_size);
+            }
+            window.redraw();
+        }
+    }
+}
+
+// 没有主题切换界面的程序启动时用：按 settings.json 里选过的主题、scheme 来，
+// 这样各个程序看起来一样；自定义主题读不到就只用自带的
+pub fn apply_saved() {
+    let settings = Settings::load(SETTINGS_FILE_PATH);
+    let themes = load_themes(THEMES_FILE_PATH).unwrap_or_else(|_| builtin_themes());
+    let theme = &themes[settings.theme_index(&themes)];
+    apply(theme, settings.scheme_for(theme));
+}
+
+// 0 号字体本来的 face，第一次换字体之前记下来；其他编号的字体不会被改，现查就行
+fn face_name(face: FontFace) -> String {
+    static DEFAULT_FACE: OnceLock<String> = OnceLock::new();
+    let default_face = DEFAULT_FACE.get_or_init(|| app::get_font(Font::Helvetica));
+    match face {
+        FontFace::Helvetica => default_face.clone(),
+        _ => app::get_font(face.font()),
+    }
+}
+
+fn scale(size: i32, old_size: i32, new_size: i32) -> i32 {
+    if old_size <= 0 {
+        return new_size;
+    }
+    (size * new_size + old_size / 2) / old_size
+}
+
+// widget 第一次被缩放前的字号，和当时的全局字号；之后总是从这里算，
+// 不然每次四舍五入的误差会越积越多，切几次主题字号就变了
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct OriginalSize {
+    size: i32,
+    base: i32,
+    // 上次设置的字号
+    applied: i32,
+}
+
+fn rescale(
+    original: Option<OriginalSize>,
+    current: i32,
+    old_size: i32,
+    new_size: i32,
+) -> OriginalSize {
+    // 没记过，或者字号被程序自己改过，以现在的为准
+    let original = match original {
+        Some(original) if original.applied == current => original,
+        _ => OriginalSize {
+            size: current,
+            base: old_size,
+            applied: current,
+        },
+    };
+    OriginalSize {
+        applied: scale(original.size, original.base, new_size),
+        ..original
+    }
+}
+
+// 按 widget 的地址记；label 和输入框里的文字分开记
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum SizeKind {
+    Label,
+    Text,
+}
+
+// 同时留一个 widget 的句柄：它知道 widget 是不是已经删掉了，
+// 删掉的每次换主题前清掉，表不会越来越大，地址被新 widget 用上也不会认错
+struct TrackedSize {
+    widget: Widget,
+    original: OriginalSize,
+}
+
+thread_local! {
+    static ORIGINAL_SIZES: RefCell<HashMap<(usize, SizeKind), TrackedSize>> =
+        RefCell::new(HashMap::new());
+}
+
+fn forget_deleted_widgets() {
+    ORIGINAL_SIZES.with(|sizes| {
+        sizes
+            .borrow_mut()
+            .retain(|_, tracked| !tracked.widget.was_deleted())
+    });
+}
+
+fn rescaled<W: WidgetExt>(
+    widget: &W,
+    kind: SizeKind,
+    current: i32,
+    old_size: i32,
+    new_size: i32,
+) -> i32 {
+    let key = (widget.as_widget_ptr() as usize, kind);
+    ORIGINAL_SIZES.with(|sizes| {
+        let mut sizes = sizes.borrow_mut();
+        let known = sizes.get(&key).map(|tracked| tracked.original);
+        let original = rescale(known, current, old_size, new_size);
+        sizes.insert(
+            key,
+            TrackedSize {
+                widget: widget.as_base_widget(),
+                original,
+            },
+        );
+        original.applied
+    })
+}
+
+fn resize_fonts(group: &Group, old_size: i32, new_size: i32) {
+    for i in 0..group.children() {
+        let Some(mut child) = group.child(i) else {
+            continue;
+        };
+        let size = rescaled(
+            &child,
+            SizeKind::Label,
+            child.label_size(),
+            old_size,
+            new_size,
+        );
+        child.set_label_size(size);
+
+        // 文字大小只有输入框、下拉框这类才有
+        if let Some(mut input) = Input::from_dyn_widget(&child) {
+            let size = rescaled(
+                &input,
+                SizeKind::Text,
+                input.text_size(),
+                old_size,
+                new_size,
+            );
+            input.set_text_size(size);
+        } else if let Some(mut choice) = Choice::from_dyn_widget(&child) {
+            let size = rescaled(
+                &choice,
+                SizeKind::Text,
+                choice.text_size(),
+                old_size,
+                new_size,
+            );
+            choice.set_text_size(size);
+        }
+
+        if let Some(group) = child.as_group() {
+            resize_fonts(&group, old_size, new_size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_from_hex() {
+        assert_eq!(
+            Rgb::try_from("#fdf6e3".to_string()),
+            Ok(Rgb(0xfd, 0xf6, 0xe3))
+        );
+        assert_eq!(
+            Rgb::try_from("#00FF7f".to_string()),
+            Ok(Rgb(0x00, 0xff, 0x7f))
+        );
+    }
+
+    #[test]
+    fn rgb_rejects_other_spellings() {
+        for text in [
+            "fdf6e3",
+            "#fff",
+            "#fdf6e3ff",
+            "#gggggg",
+            "#+1+2+3",
+            "#ééé",
+            "",
+        ] {
+            assert_eq!(
+                Rgb::try_from(text.to_string()),
+                Err(format!("`{}` is not a #rrggbb color", text)),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn parse_full_and_minimal_themes() {
+        let themes = parse_themes(
+            r##"
+            [[theme]]
+            name = "Solarized"
+            scheme = "gtk"
+            background = "#fdf6e3"
+            field = "#eee8d5"
+            foreground = "#657b83"
+            selection = "#268bd2"
+            accent = "#cb4b16"
+            font = "courier"
+            font_size = 16
+
+            [[theme]]
+            name = "Plain"
+            background = "#ffffff"
+            foreground = "#000000"
+            selection = "#0000ff"
+            accent = "#ff0000"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(themes.len(), 2);
+        assert_eq!(themes[0].scheme, SchemeName::Gtk);
+        assert_eq!(themes[0].field, Some(Rgb(0xee, 0xe8, 0xd5)));
+        assert_eq!(themes[0].font, FontFace::Courier);
+        assert_eq!(themes[0].font_size, 16);
+
+        // 没写的用默认值
+        assert_eq!(themes[1].scheme, SchemeName::Base);
+        assert_eq!(themes[1].field, None);
+        assert_eq!(themes[1].font, FontFace::Helvetica);
+        assert_eq!(themes[1].font_size, DEFAULT_FONT_SIZE);
+    }
+
+    #[test]
+    fn parse_empty_file() {
+        assert_eq!(parse_themes("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        let bad_color = parse_themes(
+            r##"
+            [[theme]]
+            name = "Bad"
+            background = "red"
+            foreground = "#000000"
+            selection = "#0000ff"
+            accent = "#ff0000"
+            "##,
+        );
+        match bad_color {
+            Err(ThemeError::Parse(message)) => assert!(message.contains("`red`"), "{}", message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        let missing = parse_themes("[[theme]]\nname = \"Half\"\n");
+        match missing {
+            Err(ThemeError::Parse(message)) => {
+                assert!(message.contains("background"), "{}", message)
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        assert!(matches!(
+            parse_themes("[[theme]]\nname = \"Oops\"\nscheme = \"motif\"\n"),
+            Err(ThemeError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn merge_replaces_in_place_and_appends_new() {
+        let builtin = builtin_themes();
+        let mut dark = builtin[1].clone();
+        dark.font_size = 20;
+        let mut custom = builtin[0].clone();
+        custom.name = "Custom".to_string();
+
+        let merged = merge_themes(builtin.clone(), vec![dark.clone(), custom.clone()]);
+        let names: Vec<&str> = merged.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Light", "Dark", "High contrast", "Custom"]);
+        assert_eq!(merged[1], dark);
+        assert_eq!(merged[0], builtin[0]);
+    }
+
+    #[test]
+    fn merge_last_duplicate_wins() {
+        let builtin = builtin_themes();
+        let mut first = builtin[0].clone();
+        first.name = "Mine".to_string();
+        let mut second = first.clone();
+        second.font_size = 30;
+
+        let merged = merge_themes(builtin, vec![first, second.clone()]);
+        assert_eq!(merged.len(), 4);
+        assert_eq!(merged[3], second);
+    }
+
+    #[test]
+    fn switching_back_and_forth_does_not_drift() {
+        // High contrast (18) -> Light (14) -> High contrast ...；每次都从原来的字号算
+        let mut original = None;
+        let mut current = 16;
+        let mut old_size = 18;
+        for _ in 0..10 {
+            for new_size in [14, 18] {
+                let next = rescale(original, current, old_size, new_size);
+                current = next.applied;
+                old_size = new_size;
+                original = Some(next);
+            }
+            assert_eq!(current, 16);
+        }
+
+        // 逐次按比例算的话，一个来回就差了 1
+        assert_eq!(scale(scale(16, 18, 14), 14, 18), 15);
+    }
+
+    #[test]
+    fn size_changed_elsewhere_becomes_the_new_original() {
+        let first = rescale(None, 12, 14, 18);
+        assert_eq!(first.applied, 15);
+
+        // 程序自己把字号改成了 20
+        let next = rescale(Some(first), 20, 18, 14);
+        assert_eq!(next.size, 20);
+        assert_eq!(next.base, 18);
+        assert_eq!(next.applied, 16);
+    }
+}
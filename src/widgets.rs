@@ -0,0 +1,307 @@
+use std::{cell::Cell, rc::Rc};
+
+use fltk::{
+    app, draw,
+    enums::{Align, Color, Event, FrameType, Key, Shortcut},
+    prelude::*,
+    widget::Widget,
+    widget_extends,
+};
+
+// 自己画的按钮：圆形、胶囊、图标三种样子
+// 悬停、按下、禁用各有颜色；Tab 可以聚焦，空格、回车触发回调；tooltip 用 set_tooltip
+// 和 Button 一样可以 set_shortcut，窗口里没有别的控件要这个键时触发回调
+//
+//   let mut ok = StyledButton::pill(80, 30, "Ok");
+//   ok.set_callback(|_| println!("ok"));
+//   let mut undo = StyledButton::icon(30, "@undo", "Undo");
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ButtonShape {
+    Round,
+    Pill,
+    // 平时只有图标，悬停、按下时才有底色
+    Icon,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct ButtonState {
+    pub hovered: bool,
+    pub pressed: bool,
+}
+
+// 底色：禁用 > 按下 > 悬停；图标按钮平时透明，返回 None
+pub fn fill_color(
+    shape: ButtonShape,
+    base: Color,
+    state: ButtonState,
+    active: bool,
+) -> Option<Color> {
+    if !active {
+        return match shape {
+            ButtonShape::Icon => None,
+            _ => Some(base.inactive()),
+        };
+    }
+    if state.pressed {
+        Some(base.darker())
+    } else if state.hovered {
+        Some(base.lighter())
+    } else {
+        match shape {
+            ButtonShape::Icon => None,
+            _ => Some(base),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct StyledButton {
+    inner: Widget,
+    shape: ButtonShape,
+    state: Rc<Cell<ButtonState>>,
+    shortcut: Rc<Cell<Shortcut>>,
+}
+
+impl StyledButton {
+    pub fn new(w: i32, h: i32, label: &str, shape: ButtonShape) -> Self {
+        let mut inner = Widget::default().with_size(w, h).with_label(label);
+        inner.set_frame(FrameType::NoBox);
+        inner.set_color(Color::Light2);
+        inner.set_selection_color(Color::Selection);
+        inner.set_align(Align::Center | Align::Inside);
+        inner.visible_focus(true);
+
+        let mut button = Self {
+            inner,
+            shape,
+            state: Rc::new(Cell::new(ButtonState::default())),
+            shortcut: Rc::new(Cell::new(Shortcut::None)),
+        };
+        button.install();
+        button
+    }
+
+    // 直径 size 的圆形按钮
+    pub fn round(size: i32, label: &str) -> Self {
+        Self::new(size, size, label, ButtonShape::Round)
+    }
+
+    // 两头是半圆的按钮
+    pub fn pill(w: i32, h: i32, label: &str) -> Self {
+        Self::new(w, h, label, ButtonShape::Pill)
+    }
+
+    // 只有图标的按钮，icon 是 FLTK 的符号，例如 "@undo"、"@>"；没有文字，tooltip 必须写
+    pub fn icon(size: i32, icon: &str, tooltip: &str) -> Self {
+        let mut button = Self::new(size, size, icon, ButtonShape::Icon);
+        button.inner.set_tooltip(tooltip);
+        button
+    }
+
+    pub fn shape(&self) -> ButtonShape {
+        self.shape
+    }
+
+    pub fn state(&self) -> ButtonState {
+        self.state.get()
+    }
+
+    pub fn shortcut(&self) -> Shortcut {
+        self.shortcut.get()
+    }
+
+    pub fn set_shortcut(&mut self, shortcut: Shortcut) {
+        self.shortcut.set(shortcut);
+    }
+
+    fn install(&mut self) {
+        let shape = self.shape;
+
+        self.inner.draw({
+            let state = self.state.clone();
+            move |w| draw_button(w, shape, state.get())
+        });
+
+        self.inner.handle({
+            let state = self.state.clone();
+            let shortcut = self.shortcut.clone();
+            move |w, ev| {
+                let mut s = state.get();
+                let handled = match ev {
+                    // Enter 要返回 true，才会收到 Leave
+                    Event::Enter => {
+                        s.hovered = true;
+                        true
+                    }
+                    Event::Leave => {
+                        s.hovered = false;
+                        true
+                    }
+                    Event::Push => {
+                        s.pressed = true;
+                        if app::visible_focus() && w.has_visible_focus() {
+                            let _ = w.take_focus();
+                        }
+                        true
+                    }
+                    // 按住拖出按钮外，松开就不算点击
+                    Event::Drag => {
+                        s.pressed = app::event_inside_widget(w);
+                        true
+                    }
+                    Event::Released => {
+                        let clicked = s.pressed && app::event_inside_widget(w);
+                        s.pressed = false;
+                        state.set(s);
+                        w.redraw();
+                        if clicked {
+                            w.do_callback();
+                        }
+                        return true;
+                    }
+                    Event::Focus | Event::Unfocus => true,
+                    Event::Shortcut if app::test_shortcut(shortcut.get()) => {
+                        w.do_callback();
+                        true
+                    }
+                    Event::KeyDown if w.has_focus() => match app::event_key() {
+                        Key::Enter | Key::KPEnter => {
+                            w.do_callback();
+                            true
+                        }
+                        k if k == Key::from_char(' ') => {
+                            w.do_callback();
+                            true
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                if handled {
+                    state.set(s);
+                    w.redraw();
+                }
+                handled
+            }
+        });
+    }
+}
+
+fn draw_button(w: &Widget, shape: ButtonShape, state: ButtonState) {
+    let (x, y, width, height) = (w.x(), w.y(), w.w(), w.h());
+    let active = w.active_r();
+
+    if let Some(color) = fill_color(shape, w.color(), state, active) {
+        draw::set_draw_color(color);
+        match shape {
+            ButtonShape::Round => draw::draw_pie(x, y, width, height, 0.0, 360.0),
+            ButtonShape::Pill => draw_pill(x, y, width, height),
+            ButtonShape::Icon => draw::draw_box(FrameType::RFlatBox, x, y, width, height, color),
+        }
+    }
+
+    // 有焦点时画一圈 selection_color
+    if w.has_focus() && app::visible_focus() && w.has_visible_focus() {
+        draw::set_draw_color(w.selection_color());
+        match shape {
+            ButtonShape::Round => draw::draw_arc(x, y, width, height, 0.0, 360.0),
+            ButtonShape::Pill => draw_pill_outline(x, y, width, height),
+            ButtonShape::Icon => draw::draw_rect(x, y, width, height),
+        }
+    }
+
+    let label_color = if active {
+        w.label_color()
+    } else {
+        w.label_color().inactive()
+    };
+    draw::set_draw_color(label_color);
+    draw::set_font(w.label_font(), w.label_size());
+    // 按下时文字往右下挪一个像素
+    let offset = if state.pressed { 1 } else { 0 };
+    draw::draw_text2(&w.label(), x + offset, y + offset, width, height, w.align());
+}
+
+fn draw_pill(x: i32, y: i32, w: i32, h: i32) {
+    let d = h.min(w);
+    draw::draw_pie(x, y, d, h, 90.0, 270.0);
+    draw::draw_pie(x + w - d, y, d, h, -90.0, 90.0);
+    draw::draw_rectf(x + d / 2, y, w - d, h);
+}
+
+fn draw_pill_outline(x: i32, y: i32, w: i32, h: i32) {
+    let d = h.min(w);
+    draw::draw_arc(x, y, d, h, 90.0, 270.0);
+    draw::draw_arc(x + w - d, y, d, h, -90.0, 90.0);
+    draw::draw_line(x + d / 2, y, x + w - d / 2, y);
+    draw::draw_line(x + d / 2, y + h - 1, x + w - d / 2, y + h - 1);
+}
+
+widget_extends!(StyledButton, Widget, inner);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Color {
+        Color::from_rgb(100, 150, 200)
+    }
+
+    fn state(hovered: bool, pressed: bool) -> ButtonState {
+        ButtonState { hovered, pressed }
+    }
+
+    #[test]
+    fn idle_buttons_use_the_base_color() {
+        for shape in [ButtonShape::Round, ButtonShape::Pill] {
+            assert_eq!(
+                fill_color(shape, base(), state(false, false), true),
+                Some(base())
+            );
+        }
+        // 图标按钮平时没有底色
+        assert_eq!(
+            fill_color(ButtonShape::Icon, base(), state(false, false), true),
+            None
+        );
+    }
+
+    #[test]
+    fn hover_is_lighter() {
+        for shape in [ButtonShape::Round, ButtonShape::Pill, ButtonShape::Icon] {
+            assert_eq!(
+                fill_color(shape, base(), state(true, false), true),
+                Some(base().lighter())
+            );
+        }
+    }
+
+    #[test]
+    fn pressed_wins_over_hover() {
+        for shape in [ButtonShape::Round, ButtonShape::Pill, ButtonShape::Icon] {
+            for hovered in [false, true] {
+                assert_eq!(
+                    fill_color(shape, base(), state(hovered, true), true),
+                    Some(base().darker())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_wins_over_everything() {
+        for s in [
+            state(false, false),
+            state(true, false),
+            state(false, true),
+            state(true, true),
+        ] {
+            assert_eq!(
+                fill_color(ButtonShape::Pill, base(), s, false),
+                Some(base().inactive())
+            );
+            assert_eq!(fill_color(ButtonShape::Icon, base(), s, false), None);
+        }
+    }
+}